@@ -1,13 +1,20 @@
 use alloy::{
     contract::{CallBuilder, CallDecoder},
-    primitives::{Bytes, Address},
+    primitives::Address,
     providers::Provider,
+    sol_types::SolValue,
 };
+use serde::Serialize;
+use suave_alloy_types::{ConfidentialBytes, SuaveTypesError};
 use super::SuaveNetwork;
 
 
 pub trait SuaveCallBuilderExt {
-    fn with_cinput(self, cinput: Bytes) -> Self;
+    fn with_cinput(self, cinput: impl Into<ConfidentialBytes>) -> Self;
+    /// Sets the confidential inputs to an ABI-encoded Solidity value, such as a `sol!` struct.
+    fn with_sol_cinput<V: SolValue + ?Sized>(self, value: &V) -> Self;
+    /// Sets the confidential inputs to `value` as JSON, ABI-encoded as `bytes`.
+    fn with_json_cinput<V: Serialize + ?Sized>(self, value: &V) -> Result<Self, SuaveTypesError> where Self: Sized;
     fn with_kettle_address(self, kettle_address: Address) -> Self;
 }
 
@@ -19,10 +26,18 @@ impl<T, P, D> SuaveCallBuilderExt for CallBuilder<T, P, D, SuaveNetwork>
         D: CallDecoder
 {
 
-    fn with_cinput(self, cinput: Bytes) -> Self {
+    fn with_cinput(self, cinput: impl Into<ConfidentialBytes>) -> Self {
         self.map(|tx| tx.with_confidential_inputs(cinput))
     }
 
+    fn with_sol_cinput<V: SolValue + ?Sized>(self, value: &V) -> Self {
+        self.with_cinput(ConfidentialBytes::abi_encode(value))
+    }
+
+    fn with_json_cinput<V: Serialize + ?Sized>(self, value: &V) -> Result<Self, SuaveTypesError> {
+        Ok(self.with_cinput(ConfidentialBytes::json_abi_encode(value)?))
+    }
+
     fn with_kettle_address(self, kettle_address: Address) -> Self {
         self.map(|tx| tx.with_kettle_address(kettle_address))
     }
@@ -1,9 +1,11 @@
 mod network;
+mod request;
 mod signer;
 mod provider;
 mod contract;
 
 pub use network::SuaveNetwork;
+pub use request::SuaveTransactionRequest;
 pub use signer::SuaveSigner;
 pub use provider::{SuaveProvider, SuaveFillProviderExt, KettleFiller};
 pub use contract::SuaveCallBuilderExt;
\ No newline at end of file
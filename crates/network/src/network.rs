@@ -1,73 +1,32 @@
-use suave_alloy_types::{ConfidentialComputeRequest, ConfidentialCallResponse};
+use suave_alloy_types::{
+    ConfidentialComputeRequest, SuaveReceiptEnvelope, SuaveTransactionReceipt, SuaveTransactionResponse,
+    SuaveTxEnvelope, SuaveTxType, SuaveTypedTransaction, SuaveTypesError, UnsignedConfidentialComputeRequest,
+};
 use alloy::{
-    network::{ BuildResult, Network, NetworkSigner, TransactionBuilder, TransactionBuilderError }, 
-    rpc::types::eth::{Header as EthHeader, TransactionReceipt},
+    network::{
+        BuildResult, Network, NetworkSigner, TransactionBuilder, TransactionBuilderError,
+        UnbuiltTransactionError,
+    }, 
+    rpc::types::eth::{Header as EthHeader, TransactionRequest},
     primitives::{Address, Bytes, ChainId, TxKind, U256}, 
-    consensus::{self, TxEnvelope}, 
+    consensus::{self, TxType}, 
     eips::eip2930::AccessList,
-    eips::eip2718::Eip2718Error,
 };
+use super::request::SuaveTransactionRequest;
 
 
-#[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum SuaveTxType {
-    /// Legacy transaction type.
-    Legacy = 0,
-    /// EIP-2930 transaction type.
-    Eip2930 = 1,
-    /// EIP-1559 transaction type.
-    Eip1559 = 2,
-    /// EIP-4844 transaction type.
-    Eip4844 = 3,
-    /// SUAVE "transaction" type
-    ConfidentialComputeRequest = 4,
-}
-
-impl From<SuaveTxType> for u8 {
-    fn from(value: SuaveTxType) -> Self {
-        value as u8
-    }
-}
-
-impl TryFrom<u8> for SuaveTxType {
-    type Error = Eip2718Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        Ok(match value {
-            0 => SuaveTxType::Legacy,
-            1 => SuaveTxType::Eip2930,
-            2 => SuaveTxType::Eip1559,
-            3 => SuaveTxType::Eip4844,
-            _ => return Err(Eip2718Error::UnexpectedType(value)),
-        })
-    }
-}
-
-impl std::fmt::Display for SuaveTxType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SuaveTxType::Legacy => write!(f, "Legacy"),
-            SuaveTxType::Eip2930 => write!(f, "EIP-2930"),
-            SuaveTxType::Eip1559 => write!(f, "EIP-1559"),
-            SuaveTxType::Eip4844 => write!(f, "EIP-4844"),
-            SuaveTxType::ConfidentialComputeRequest => write!(f, "ConfidentialComputeRequest"),
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 pub struct SuaveNetwork;
 
 impl Network for SuaveNetwork {
     type TxType = SuaveTxType;
-    type TxEnvelope = ConfidentialComputeRequest;
-    type UnsignedTx = ConfidentialComputeRequest;
-    type ReceiptEnvelope = TxEnvelope;
+    type TxEnvelope = SuaveTxEnvelope;
+    type UnsignedTx = SuaveTypedTransaction;
+    type ReceiptEnvelope = SuaveReceiptEnvelope;
     type Header = consensus::Header;
-    type TransactionRequest = ConfidentialComputeRequest;
-    type TransactionResponse = ConfidentialCallResponse;
-    type ReceiptResponse = TransactionReceipt;
+    type TransactionRequest = SuaveTransactionRequest;
+    type TransactionResponse = SuaveTransactionResponse;
+    type ReceiptResponse = SuaveTransactionReceipt;
     type HeaderResponse = EthHeader;
 }
 
@@ -101,10 +60,7 @@ impl TransactionBuilder<SuaveNetwork> for ConfidentialComputeRequest {
 
     fn from(&self) -> Option<Address> {
         self.confidential_compute_record.from
-        // self.confidential_compute_record.signature.map(|sig| {
-        //     let prehash = self.signature_hash();
-        //     sig.recover_address_from_prehash(&B256::from(prehash)).expect("Invalid signature")
-        // })
+            .or_else(|| self.recover_signer().ok())
     }
 
     fn set_from(&mut self, from: Address) {
@@ -178,22 +134,28 @@ impl TransactionBuilder<SuaveNetwork> for ConfidentialComputeRequest {
     }
 
     fn build_unsigned(self) -> SuaveBuildResult<<SuaveNetwork as Network>::UnsignedTx>{
-        // todo: Instead of returning CCR with optional fields, return a struct with required fields
-        Ok(self)
+        let ty = self.output_tx_type();
+        if let Err(missing) = self.complete_type(ty) {
+            return Err(UnbuiltTransactionError {
+                request: self.into(),
+                error: TransactionBuilderError::InvalidTransactionRequest(ty, missing),
+            });
+        }
+        match UnsignedConfidentialComputeRequest::try_from(self.clone()) {
+            Ok(tx) => Ok(tx.into()),
+            Err(err) => Err(UnbuiltTransactionError {
+                request: self.into(),
+                error: builder_error(ty, err),
+            }),
+        }
     }
 
     async fn build<S: NetworkSigner<SuaveNetwork>>(
         self,
         signer: &S,
     ) -> Result<<SuaveNetwork as Network>::TxEnvelope, TransactionBuilderError<SuaveNetwork>> {
-        match self.build_unsigned() {
-            Ok(tx) => {
-                signer.sign_transaction(tx).await.map_err(|e| e.into())
-            },
-            Err(e) => {
-                todo!() // todo: handle (why is this different err than build_unsigned?)
-            }, 
-        }
+        let tx = self.build_unsigned().map_err(|e| e.error)?;
+        signer.sign_transaction(tx).await.map_err(|e| e.into())
     }
 
     fn access_list(&self) -> Option<&AccessList> {
@@ -208,9 +170,40 @@ impl TransactionBuilder<SuaveNetwork> for ConfidentialComputeRequest {
         None
     }
 
-    // todo: implement types
-    fn complete_type(&self, ty: SuaveTxType) -> Result<(), Vec<&'static str>> {
-        unimplemented!("complete_type")
+    fn complete_type(&self, _ty: SuaveTxType) -> Result<(), Vec<&'static str>> {
+        let record = &self.confidential_compute_record;
+        let mut missing = Vec::new();
+        if record.nonce.is_none() {
+            missing.push("nonce");
+        }
+        if record.gas.is_none() {
+            missing.push("gas_limit");
+        }
+        if record.gas_price.is_none() {
+            missing.push("gas_price");
+        }
+        if record.chain_id.is_none() {
+            missing.push("chain_id");
+        }
+        if record.kettle_address.is_none() {
+            missing.push("kettle_address");
+        }
+        // Invalid fields are reported alongside missing ones, the builder error only carries names.
+        if record.gas.is_some_and(|gas| gas > u64::MAX as u128) {
+            missing.push("gas_limit");
+        }
+        if record.is_eip712 && !record.spec().eip712_records {
+            missing.push("is_eip712");
+        }
+        let cinputs_hash = self.confidential_inputs.hash();
+        if record.confidential_inputs_hash.is_some_and(|hash| hash != cinputs_hash) {
+            missing.push("confidential_inputs_hash");
+        }
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
     }
 
     fn can_submit(&self) -> bool {
@@ -224,15 +217,276 @@ impl TransactionBuilder<SuaveNetwork> for ConfidentialComputeRequest {
     }
 
     fn output_tx_type(&self) -> SuaveTxType {
-        panic!("Not supported")
+        SuaveTxType::ConfidentialComputeRequest
+    }
+
+    fn output_tx_type_checked(&self) -> Option<SuaveTxType> {
+        self.can_build().then_some(SuaveTxType::ConfidentialComputeRequest)
+    }
+
+    fn prep_for_submission(&mut self) {}
+
+}
+
+// Dispatches a builder call to the inner confidential or Ethereum request.
+macro_rules! delegate {
+    ($request:expr, $tx:ident => $body:expr) => {
+        match $request {
+            SuaveTransactionRequest::ConfidentialComputeRequest($tx) => $body,
+            SuaveTransactionRequest::Ethereum($tx) => $body,
+        }
+    };
+}
+
+impl TransactionBuilder<SuaveNetwork> for SuaveTransactionRequest {
+
+    fn chain_id(&self) -> Option<ChainId> {
+        delegate!(self, tx => tx.chain_id())
+    }
+
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        delegate!(self, tx => tx.set_chain_id(chain_id))
+    }
+
+    fn nonce(&self) -> Option<u64> {
+        delegate!(self, tx => tx.nonce())
+    }
+
+    fn set_nonce(&mut self, nonce: u64) {
+        delegate!(self, tx => tx.set_nonce(nonce))
+    }
+
+    fn input(&self) -> Option<&Bytes> {
+        delegate!(self, tx => tx.input())
+    }
+
+    fn set_input(&mut self, input: Bytes) {
+        delegate!(self, tx => tx.set_input(input))
+    }
+
+    fn from(&self) -> Option<Address> {
+        delegate!(self, tx => tx.from())
+    }
+
+    fn set_from(&mut self, from: Address) {
+        delegate!(self, tx => tx.set_from(from))
+    }
+
+    fn to(&self) -> Option<TxKind> {
+        delegate!(self, tx => tx.to())
+    }
+
+    fn set_to(&mut self, to: TxKind) {
+        delegate!(self, tx => tx.set_to(to))
+    }
+
+    fn value(&self) -> Option<U256> {
+        delegate!(self, tx => tx.value())
+    }
+
+    fn set_value(&mut self, value: U256) {
+        delegate!(self, tx => tx.set_value(value))
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        delegate!(self, tx => tx.gas_price())
+    }
+
+    fn set_gas_price(&mut self, gas_price: u128) {
+        delegate!(self, tx => tx.set_gas_price(gas_price))
+    }
+
+    fn max_fee_per_gas(&self) -> Option<u128> {
+        delegate!(self, tx => tx.max_fee_per_gas())
+    }
+
+    fn set_max_fee_per_gas(&mut self, max_fee_per_gas: u128) {
+        delegate!(self, tx => tx.set_max_fee_per_gas(max_fee_per_gas))
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        delegate!(self, tx => tx.max_priority_fee_per_gas())
+    }
+
+    fn set_max_priority_fee_per_gas(&mut self, max_priority_fee_per_gas: u128) {
+        delegate!(self, tx => tx.set_max_priority_fee_per_gas(max_priority_fee_per_gas))
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        delegate!(self, tx => tx.max_fee_per_blob_gas())
+    }
+
+    fn set_max_fee_per_blob_gas(&mut self, max_fee_per_blob_gas: u128) {
+        delegate!(self, tx => tx.set_max_fee_per_blob_gas(max_fee_per_blob_gas))
+    }
+
+    fn gas_limit(&self) -> Option<u128> {
+        delegate!(self, tx => tx.gas_limit())
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: u128) {
+        delegate!(self, tx => tx.set_gas_limit(gas_limit))
+    }
+
+    fn set_blob_sidecar(&mut self, blob_sidecar: consensus::BlobTransactionSidecar) {
+        delegate!(self, tx => tx.set_blob_sidecar(blob_sidecar))
+    }
+
+    fn build_unsigned(self) -> SuaveBuildResult<<SuaveNetwork as Network>::UnsignedTx> {
+        let ty = self.output_tx_type();
+        if let Err(missing) = self.complete_type(ty) {
+            return Err(UnbuiltTransactionError {
+                request: self,
+                error: TransactionBuilderError::InvalidTransactionRequest(ty, missing),
+            });
+        }
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => tx.build_unsigned(),
+            SuaveTransactionRequest::Ethereum(tx) => {
+                let typed = match tx.build_typed_tx() {
+                    Ok(typed) => typed,
+                    Err(tx) => {
+                        let missing = tx.complete_type(tx.output_tx_type()).err().unwrap_or_default();
+                        return Err(UnbuiltTransactionError {
+                            request: tx.into(),
+                            error: TransactionBuilderError::InvalidTransactionRequest(ty, missing),
+                        });
+                    }
+                };
+                SuaveTypedTransaction::try_from(typed.clone()).map_err(|err| UnbuiltTransactionError {
+                    request: TransactionRequest::from(typed).into(),
+                    error: builder_error(ty, err),
+                })
+            }
+        }
+    }
+
+    async fn build<S: NetworkSigner<SuaveNetwork>>(
+        self,
+        signer: &S,
+    ) -> Result<<SuaveNetwork as Network>::TxEnvelope, TransactionBuilderError<SuaveNetwork>> {
+        let tx = self.build_unsigned().map_err(|e| e.error)?;
+        signer.sign_transaction(tx).await.map_err(|e| e.into())
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        delegate!(self, tx => tx.access_list())
+    }
+
+    fn set_access_list(&mut self, access_list: AccessList) {
+        delegate!(self, tx => tx.set_access_list(access_list))
+    }
+
+    fn blob_sidecar(&self) -> Option<&consensus::BlobTransactionSidecar> {
+        delegate!(self, tx => tx.blob_sidecar())
+    }
+
+    fn complete_type(&self, ty: SuaveTxType) -> Result<(), Vec<&'static str>> {
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => tx.complete_type(ty),
+            SuaveTransactionRequest::Ethereum(tx) => {
+                let checked = match TxType::try_from(u8::from(ty)) {
+                    Ok(ty) => tx.complete_type(ty),
+                    // Confidential types need what the request would miss once made confidential.
+                    Err(_) => match self.clone().make_confidential() {
+                        Some(tx) => tx.complete_type(ty),
+                        None => Err(vec!["to"]),
+                    },
+                };
+                let mut missing = checked.err().unwrap_or_default();
+                missing.extend(blob_fields(tx));
+                if missing.is_empty() {
+                    Ok(())
+                } else {
+                    Err(missing)
+                }
+            }
+        }
+    }
+
+    fn can_submit(&self) -> bool {
+        delegate!(self, tx => tx.can_submit())
+    }
+
+    fn can_build(&self) -> bool {
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => tx.can_build(),
+            SuaveTransactionRequest::Ethereum(tx) => tx.can_build() && blob_fields(tx).is_empty(),
+        }
+    }
+
+    /// Blob requests report EIP-1559, the closest type SUAVE accepts, and fail to build on their blob fields.
+    fn output_tx_type(&self) -> SuaveTxType {
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => tx.output_tx_type(),
+            SuaveTransactionRequest::Ethereum(tx) => SuaveTxType::try_from(u8::from(tx.output_tx_type()))
+                .unwrap_or(SuaveTxType::Eip1559),
+        }
     }
 
     fn output_tx_type_checked(&self) -> Option<SuaveTxType> {
-        panic!("Not supported")
+        self.can_build().then(|| self.output_tx_type())
     }
 
     fn prep_for_submission(&mut self) {
-        unimplemented!("prep_for_submission")
+        delegate!(self, tx => tx.prep_for_submission())
     }
 
-}
\ No newline at end of file
+}
+
+// Missing fields are reported like `complete_type` does, anything else as is.
+fn builder_error(ty: SuaveTxType, err: SuaveTypesError) -> TransactionBuilderError<SuaveNetwork> {
+    match err {
+        SuaveTypesError::MissingField(field) => TransactionBuilderError::InvalidTransactionRequest(ty, vec![field]),
+        err => TransactionBuilderError::Custom(Box::new(err)),
+    }
+}
+
+// SUAVE has no blob transactions, so their fields can't be built into any type.
+fn blob_fields(tx: &TransactionRequest) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if tx.max_fee_per_blob_gas.is_some() {
+        fields.push("max_fee_per_blob_gas");
+    }
+    if tx.blob_versioned_hashes.is_some() {
+        fields.push("blob_versioned_hashes");
+    }
+    if tx.sidecar.is_some() {
+        fields.push("sidecar");
+    }
+    fields
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    #[test]
+    fn test_ethereum_request_unbuildable_fields() {
+        let to = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
+        let tx = TransactionRequest::default()
+            .with_to(to.into())
+            .with_nonce(1)
+            .with_chain_id(0x067932)
+            .with_gas_limit(21000)
+            .with_max_fee_per_gas(1)
+            .with_max_priority_fee_per_gas(1)
+            .with_max_fee_per_blob_gas(1);
+        let request = SuaveTransactionRequest::from(tx);
+        assert!(!request.can_build());
+        assert_eq!(request.output_tx_type(), SuaveTxType::Eip1559);
+        assert_eq!(request.output_tx_type_checked(), None);
+        let err = request.build_unsigned().unwrap_err().error;
+        assert!(matches!(
+            err,
+            TransactionBuilderError::InvalidTransactionRequest(SuaveTxType::Eip1559, missing) if missing == vec!["max_fee_per_blob_gas"]
+        ));
+
+        let request = SuaveTransactionRequest::from(TransactionRequest::default().with_to(to.into()).with_nonce(1));
+        let missing = request.complete_type(SuaveTxType::ConfidentialComputeRequest).unwrap_err();
+        assert_eq!(missing, vec!["gas_limit", "gas_price", "chain_id", "kettle_address"]);
+    }
+
+}
@@ -2,15 +2,16 @@ use reqwest::Client as ReqwestClient;
 use std::sync::{Arc, OnceLock};
 use std::str::FromStr;
 use alloy::{
-    transports::{http::Http, Transport, TransportResult},
+    transports::{http::Http, Transport, TransportErrorKind, TransportResult},
     providers::{
         fillers::{FillProvider, FillerControlFlow, TxFiller}, 
         Provider, ProviderBuilder, RootProvider, SendableTx,
     },
     rpc::client::ClientRef, 
-    primitives::Address, 
+    primitives::{Address, B256}, 
     network::Network, 
 };
+use suave_alloy_types::SuaveTransactionReceipt;
 use super::network::SuaveNetwork;
 
 
@@ -31,6 +32,19 @@ impl<T> SuaveProvider<T>
     pub async fn kettle_address(&self) -> TransportResult<Address> {
         kettle_address(self.client()).await
     }
+
+    /// Receipt of the SuaveTransaction that executed the request `request_hash`, linked to its
+    /// request record, or `None` while the request is pending.
+    pub async fn get_request_receipt(&self, request_hash: B256) -> TransportResult<Option<SuaveTransactionReceipt>> {
+        let Some(mut receipt) = self.get_transaction_receipt(request_hash).await? else {
+            return Ok(None);
+        };
+        let response = self.get_transaction_by_hash(receipt.transaction_hash()).await?;
+        let response = response.as_confidential_call()
+            .ok_or_else(|| TransportErrorKind::custom_str("receipt is not for a SuaveTransaction"))?;
+        receipt.link_response(response).map_err(TransportErrorKind::custom)?;
+        Ok(Some(receipt))
+    }
 }
 
 type ReqwestHttp = Http<ReqwestClient>;
@@ -107,7 +121,7 @@ impl TxFiller<SuaveNetwork> for KettleFiller {
     type Fillable = Address;
 
     fn status(&self, tx: &<SuaveNetwork as Network>::TransactionRequest) -> FillerControlFlow {
-        if tx.kettle_address().is_some() {
+        if !tx.is_confidential() || tx.kettle_address().is_some() {
             FillerControlFlow::Finished
         } else {
             FillerControlFlow::Ready
@@ -139,7 +153,7 @@ impl TxFiller<SuaveNetwork> for KettleFiller {
         mut tx: SendableTx<SuaveNetwork>,
     ) -> TransportResult<SendableTx<SuaveNetwork>> {
         if let Some(builder) = tx.as_mut_builder() {
-            if builder.kettle_address().is_none() {
+            if builder.is_confidential() && builder.kettle_address().is_none() {
                 builder.set_kettle_address(fillable)
             }
         };
@@ -159,6 +173,7 @@ async fn kettle_address<'a, T>(client: ClientRef<'a , T>) -> TransportResult<Add
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use alloy::primitives::b256;
     use eyre::Result;
     use super::*;
 
@@ -170,4 +185,16 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_receipt_by_request_hash() -> Result<()> {
+        let provider = SuaveProvider::try_from("https://rpc.rigil.suave.flashbots.net")?;
+        let request_hash = b256!("3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9");
+        let receipt = provider.get_request_receipt(request_hash).await?
+            .expect("mined request has a receipt");
+        assert!(receipt.is_suave_transaction());
+        assert_eq!(receipt.transaction_hash(), b256!("82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2"));
+        assert_eq!(receipt.request_record_hash, Some(request_hash));
+        Ok(())
+    }
+
 }
\ No newline at end of file
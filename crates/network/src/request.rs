@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use alloy::{
+    consensus::{TxEnvelope, TypedTransaction},
+    primitives::Address,
+    rpc::types::eth::TransactionRequest,
+};
+use suave_alloy_types::{
+    ConfidentialBytes, ConfidentialComputeRecord, ConfidentialComputeRequest, SuaveTxEnvelope, SuaveTypedTransaction,
+};
+
+
+/// Transaction request for the SUAVE network, either a confidential compute request
+/// or an ordinary Ethereum transaction request (deployments, transfers).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SuaveTransactionRequest {
+    ConfidentialComputeRequest(ConfidentialComputeRequest),
+    Ethereum(TransactionRequest),
+}
+
+impl Default for SuaveTransactionRequest {
+    fn default() -> Self {
+        SuaveTransactionRequest::ConfidentialComputeRequest(ConfidentialComputeRequest::default())
+    }
+}
+
+impl SuaveTransactionRequest {
+
+    pub fn is_confidential(&self) -> bool {
+        matches!(self, SuaveTransactionRequest::ConfidentialComputeRequest(_))
+    }
+
+    pub fn as_confidential(&self) -> Option<&ConfidentialComputeRequest> {
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => Some(tx),
+            SuaveTransactionRequest::Ethereum(_) => None,
+        }
+    }
+
+    pub fn kettle_address(&self) -> Option<Address> {
+        self.as_confidential().and_then(|tx| tx.kettle_address())
+    }
+
+    /// Sets the kettle address, turning an Ethereum request into a confidential compute request.
+    /// Contract creations stay Ethereum requests and are left unchanged.
+    pub fn with_kettle_address(mut self, kettle_address: Address) -> Self {
+        self.set_kettle_address(kettle_address);
+        self
+    }
+
+    /// Sets the kettle address, turning an Ethereum request into a confidential compute request.
+    /// Contract creations stay Ethereum requests and are left unchanged.
+    pub fn set_kettle_address(&mut self, kettle_address: Address) {
+        if let Some(tx) = self.make_confidential() {
+            tx.set_kettle_address(kettle_address);
+        }
+    }
+
+    /// Sets the confidential inputs, turning an Ethereum request into a confidential compute request.
+    /// Contract creations stay Ethereum requests and are left unchanged.
+    pub fn with_confidential_inputs(mut self, confidential_inputs: impl Into<ConfidentialBytes>) -> Self {
+        self.set_confidential_inputs(confidential_inputs);
+        self
+    }
+
+    /// Sets the confidential inputs, turning an Ethereum request into a confidential compute request.
+    /// Contract creations stay Ethereum requests and are left unchanged.
+    pub fn set_confidential_inputs(&mut self, confidential_inputs: impl Into<ConfidentialBytes>) {
+        if let Some(tx) = self.make_confidential() {
+            tx.set_confidential_inputs(confidential_inputs);
+        }
+    }
+
+    /// Returns `None` for contract creations, which a confidential compute request can't express.
+    pub(crate) fn make_confidential(&mut self) -> Option<&mut ConfidentialComputeRequest> {
+        if let SuaveTransactionRequest::Ethereum(tx) = self {
+            let to = tx.to?;
+            let tx = std::mem::take(tx);
+            let record = ConfidentialComputeRecord {
+                nonce: tx.nonce,
+                to,
+                gas: tx.gas,
+                gas_price: tx.gas_price,
+                value: tx.value.unwrap_or_default(),
+                input: tx.input.input.unwrap_or_default(),
+                chain_id: tx.chain_id,
+                from: tx.from,
+                ..ConfidentialComputeRecord::default()
+            };
+            *self = ConfidentialComputeRequest::new(record, None).into();
+        }
+        match self {
+            SuaveTransactionRequest::ConfidentialComputeRequest(tx) => Some(tx),
+            SuaveTransactionRequest::Ethereum(_) => unreachable!("converted above"),
+        }
+    }
+
+}
+
+impl From<ConfidentialComputeRequest> for SuaveTransactionRequest {
+    fn from(tx: ConfidentialComputeRequest) -> Self {
+        SuaveTransactionRequest::ConfidentialComputeRequest(tx)
+    }
+}
+
+impl From<TransactionRequest> for SuaveTransactionRequest {
+    fn from(tx: TransactionRequest) -> Self {
+        SuaveTransactionRequest::Ethereum(tx)
+    }
+}
+
+impl From<SuaveTypedTransaction> for SuaveTransactionRequest {
+    fn from(tx: SuaveTypedTransaction) -> Self {
+        match tx {
+            SuaveTypedTransaction::Legacy(tx) => TransactionRequest::from(TypedTransaction::from(tx)).into(),
+            SuaveTypedTransaction::Eip2930(tx) => TransactionRequest::from(TypedTransaction::from(tx)).into(),
+            SuaveTypedTransaction::Eip1559(tx) => TransactionRequest::from(TypedTransaction::from(tx)).into(),
+            SuaveTypedTransaction::ConfidentialComputeRecord(record) => record_request(record.into()).into(),
+            SuaveTypedTransaction::ConfidentialComputeRequest(tx) => ConfidentialComputeRequest::from(tx).into(),
+            SuaveTypedTransaction::SuaveTransaction(tx) => record_request(tx.request_record().clone().into_record()).into(),
+        }
+    }
+}
+
+impl From<SuaveTxEnvelope> for SuaveTransactionRequest {
+    fn from(envelope: SuaveTxEnvelope) -> Self {
+        match envelope {
+            SuaveTxEnvelope::Legacy(tx) => TransactionRequest::from(TxEnvelope::from(tx)).into(),
+            SuaveTxEnvelope::Eip2930(tx) => TransactionRequest::from(TxEnvelope::from(tx)).into(),
+            SuaveTxEnvelope::Eip1559(tx) => TransactionRequest::from(TxEnvelope::from(tx)).into(),
+            SuaveTxEnvelope::ConfidentialComputeRecord(tx) => record_request(tx.into_record()).into(),
+            SuaveTxEnvelope::ConfidentialComputeRequest(tx) => tx.into_request().into(),
+            SuaveTxEnvelope::SuaveTransaction(tx) => record_request(tx.request_record().clone().into_record()).into(),
+        }
+    }
+}
+
+// Keeps the record as is, including its confidential inputs hash, since the inputs are not known.
+fn record_request(confidential_compute_record: ConfidentialComputeRecord) -> ConfidentialComputeRequest {
+    ConfidentialComputeRequest {
+        confidential_compute_record,
+        confidential_inputs: ConfidentialBytes::new(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use alloy::{
+        network::TransactionBuilder,
+        primitives::{Bytes, U256},
+    };
+    use super::*;
+
+    #[test]
+    fn test_ethereum_request_to_confidential() {
+        let to = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
+        let kettle = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let tx = TransactionRequest::default()
+            .with_to(to.into())
+            .with_nonce(3)
+            .with_value(U256::from(1));
+        let request = SuaveTransactionRequest::from(tx);
+        assert!(!request.is_confidential());
+        assert_eq!(request.kettle_address(), None);
+
+        let request = request
+            .with_kettle_address(kettle)
+            .with_confidential_inputs(Bytes::from_static(b"confidential"));
+        let ccr = request.as_confidential().expect("confidential request");
+        assert_eq!(ccr.kettle_address(), Some(kettle));
+        assert_eq!(ccr.confidential_compute_record.to, to);
+        assert_eq!(ccr.confidential_compute_record.nonce, Some(3));
+        assert_eq!(ccr.confidential_compute_record.value, U256::from(1));
+        assert_eq!(ccr.confidential_inputs.expose(), b"confidential");
+    }
+
+    #[test]
+    fn test_contract_creation_stays_ethereum() {
+        let kettle = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        // No `to`, so a contract creation.
+        let tx = TransactionRequest::default().with_nonce(3);
+        let request = SuaveTransactionRequest::from(tx.clone())
+            .with_kettle_address(kettle)
+            .with_confidential_inputs(Bytes::from_static(b"confidential"));
+        assert!(!request.is_confidential());
+        assert_eq!(request, SuaveTransactionRequest::Ethereum(tx));
+    }
+
+}
@@ -7,8 +7,11 @@ use alloy::{
     signers::{Result as SignerResult, Error as SignerError},
     network::{TxSigner, NetworkSigner},
     primitives::{Address, Signature},
+    consensus::SignableTransaction,
+};
+use suave_alloy_types::{
+    SignedConfidentialComputeRequest, SuaveTxEnvelope, SuaveTypedTransaction, UnsignedConfidentialComputeRequest,
 };
-use suave_alloy_types::ConfidentialComputeRequest;
 use crate::SuaveNetwork;
 
 
@@ -16,6 +19,7 @@ use crate::SuaveNetwork;
 pub struct SuaveSigner {
     default_signer: Address,
     signers: BTreeMap<Address, Arc<dyn TxSigner<Signature> + Send + Sync>>,
+    eip712: bool,
 }
 
 impl SuaveSigner {
@@ -27,31 +31,44 @@ impl SuaveSigner {
         let mut this = Self {
             default_signer: signer.address(),
             signers: BTreeMap::new(),
+            eip712: false,
         };
         this.register_signer(signer);
         this
     }
 
+    /// Sign records as EIP-712 typed data instead of the 0x42-prefixed RLP hash,
+    /// on networks that support it.
+    pub fn with_eip712(mut self, eip712: bool) -> Self {
+        self.eip712 = eip712;
+        self
+    }
+
     pub fn register_signer(&mut self, signer: Arc<dyn TxSigner<Signature> + Send + Sync>) {
         self.signers.insert(signer.address(), signer);
     }
 
-    pub async fn sign_transaction(&self, tx: &mut ConfidentialComputeRequest) -> SignerResult<ConfidentialComputeRequest> {
+    pub async fn sign_transaction(
+        &self,
+        tx: UnsignedConfidentialComputeRequest,
+    ) -> SignerResult<SignedConfidentialComputeRequest> {
         self.sign_transaction_from(self.default_signer, tx).await
     }
 
     async fn sign_transaction_from(
         &self,
         sender: Address,
-        tx: &mut ConfidentialComputeRequest,
-    ) -> SignerResult<ConfidentialComputeRequest> {
-        self.signers.get(&sender)
+        mut tx: UnsignedConfidentialComputeRequest,
+    ) -> SignerResult<SignedConfidentialComputeRequest> {
+        if self.eip712 && tx.spec().eip712_records {
+            tx.set_eip712(true).map_err(SignerError::other)?;
+        }
+        // Fails on an `is_eip712` flag the network doesn't support.
+        tx.record_signing_hash().map_err(SignerError::other)?;
+        let signature = self.signers.get(&sender)
             .ok_or(SignerError::other("unknown signer"))?
-            .sign_transaction(tx).await.map(|sig| {
-                tx.confidential_compute_record.set_sig(sig);
-                tx.confidential_compute_record.from = Some(sender);
-                tx.clone()
-            })
+            .sign_transaction(&mut tx).await?;
+        Ok(tx.into_signed(signature).into())
     }
 }
 
@@ -74,9 +91,33 @@ impl NetworkSigner<SuaveNetwork> for SuaveSigner {
     async fn sign_transaction_from(
         &self,
         sender: Address,
-        mut tx: ConfidentialComputeRequest,
-    ) -> SignerResult<ConfidentialComputeRequest> {
-        self.sign_transaction_from(sender, &mut tx).await
+        tx: SuaveTypedTransaction,
+    ) -> SignerResult<SuaveTxEnvelope> {
+        let mut tx = match tx {
+            SuaveTypedTransaction::ConfidentialComputeRequest(tx) => {
+                return self.sign_transaction_from(sender, tx).await.map(Into::into);
+            }
+            tx => tx,
+        };
+        let signer = self.signers.get(&sender)
+            .ok_or(SignerError::other("unknown signer"))?;
+        let signature = match &mut tx {
+            SuaveTypedTransaction::Legacy(tx) => signer.sign_transaction(tx).await?,
+            SuaveTypedTransaction::Eip2930(tx) => signer.sign_transaction(tx).await?,
+            SuaveTypedTransaction::Eip1559(tx) => signer.sign_transaction(tx).await?,
+            SuaveTypedTransaction::ConfidentialComputeRecord(record) => {
+                if self.eip712 && record.spec().eip712_records {
+                    record.set_eip712(true).map_err(SignerError::other)?;
+                }
+                record.record_signing_hash().map_err(SignerError::other)?;
+                signer.sign_transaction(record).await?
+            }
+            SuaveTypedTransaction::ConfidentialComputeRequest(_) => unreachable!("signed above"),
+            SuaveTypedTransaction::SuaveTransaction(_) => {
+                return Err(SignerError::other("SuaveTransactions are signed by kettles"));
+            }
+        };
+        Ok(tx.into_signed(signature))
     }
 
 }
@@ -87,6 +128,7 @@ impl std::fmt::Debug for SuaveSigner {
         f.debug_struct("SuaveSigner")
             .field("default_signer", &self.default_signer)
             .field("signers", &signers_add)
+            .field("eip712", &self.eip712)
             .finish()
     }
 }
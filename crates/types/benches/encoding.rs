@@ -0,0 +1,63 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use alloy::{
+    consensus::SignableTransaction,
+    eips::eip2718::Encodable2718,
+    primitives::{Address, Bytes, Signature, U256},
+};
+use suave_alloy_types::{ConfidentialComputeRecord, ConfidentialComputeRequest, SignedConfidentialComputeRequest};
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 512 * 1024];
+
+fn signed_request(size: usize) -> SignedConfidentialComputeRequest {
+    let signature = Signature::from_rs_and_parity(U256::from(1), U256::from(1), false).unwrap();
+    let record = ConfidentialComputeRecord {
+        nonce: Some(0x22),
+        to: Address::repeat_byte(0x11),
+        gas: Some(0x0f4240),
+        gas_price: Some(0x3b9aca00),
+        input: Bytes::from(vec![0xab; size]),
+        kettle_address: Some(Address::repeat_byte(0x22)),
+        chain_id: Some(0x067932),
+        signature: Some(signature),
+        ..ConfidentialComputeRecord::default()
+    };
+    let cc_request = ConfidentialComputeRequest::new(record, Some(vec![0xcd; size].into()));
+    SignedConfidentialComputeRequest::try_from(cc_request).unwrap()
+}
+
+fn encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ccr");
+    for size in SIZES {
+        let signed = signed_request(size);
+        let unsigned = signed.clone().strip_signature();
+        group.throughput(Throughput::Bytes(2 * size as u64));
+
+        group.bench_with_input(BenchmarkId::new("encode_2718_len", size), &signed, |b, signed| {
+            b.iter(|| black_box(signed.encode_2718_len()))
+        });
+        group.bench_with_input(BenchmarkId::new("encode_2718", size), &signed, |b, signed| {
+            b.iter(|| {
+                let mut out = Vec::with_capacity(signed.encode_2718_len());
+                signed.encode_2718(&mut out);
+                black_box(out)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("rlp_encode", size), &signed, |b, signed| {
+            b.iter(|| black_box(signed.rlp_encode()))
+        });
+        group.bench_with_input(BenchmarkId::new("encode_for_signing", size), &unsigned, |b, unsigned| {
+            b.iter(|| {
+                let mut out = Vec::with_capacity(unsigned.payload_len_for_signature());
+                unsigned.encode_for_signing(&mut out);
+                black_box(out)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("record_signing_hash", size), &unsigned, |b, unsigned| {
+            b.iter(|| black_box(unsigned.record_signing_hash()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encoding);
+criterion_main!(benches);
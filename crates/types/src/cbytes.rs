@@ -0,0 +1,250 @@
+use core::fmt;
+use alloc::{string::ToString, vec::Vec};
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_sol_types::{SolType, SolValue};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use super::error::{Result, SuaveTypesError};
+
+
+/// Confidential inputs of a request. Formatting never shows the bytes and they are wiped
+/// on drop; read them with [`ConfidentialBytes::expose`].
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ConfidentialBytes(Vec<u8>);
+
+impl ConfidentialBytes {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// The raw bytes. Keep them out of logs.
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Copies the raw bytes into `Bytes`, which won't be zeroized.
+    pub fn to_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(&self.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The confidential inputs hash committed to by the record.
+    pub fn hash(&self) -> B256 {
+        keccak256(&self.0)
+    }
+
+    /// ABI-encodes a Solidity value, such as a `sol!` struct.
+    pub fn abi_encode<T: SolValue + ?Sized>(value: &T) -> Self {
+        Self(value.abi_encode())
+    }
+
+    /// Serializes `value` to JSON and ABI-encodes it as `bytes`, the layout of bundles sent to
+    /// kettles.
+    #[cfg(feature = "serde")]
+    pub fn json_abi_encode<T: serde::Serialize + ?Sized>(value: &T) -> Result<Self> {
+        let json = zeroize::Zeroizing::new(serde_json::to_vec(value).map_err(malformed)?);
+        Ok(Self::abi_encode(json.as_slice()))
+    }
+
+    pub fn abi_decode<T>(&self) -> Result<T>
+    where
+        T: SolValue + From<<T::SolType as SolType>::RustType>,
+    {
+        T::abi_decode(&self.0, true).map_err(malformed)
+    }
+
+    /// Reverses [`ConfidentialBytes::json_abi_encode`].
+    #[cfg(feature = "serde")]
+    pub fn json_abi_decode<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let json = zeroize::Zeroizing::new(Vec::from(self.abi_decode::<Bytes>()?.0));
+        serde_json::from_slice(&json).map_err(malformed)
+    }
+}
+
+fn malformed(err: impl fmt::Display) -> SuaveTypesError {
+    SuaveTypesError::MalformedConfidentialInputs(err.to_string())
+}
+
+impl Drop for ConfidentialBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Zeroize for ConfidentialBytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ConfidentialBytes {}
+
+impl fmt::Debug for ConfidentialBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ConfidentialBytes(<redacted>)")
+    }
+}
+
+impl fmt::Display for ConfidentialBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl From<Vec<u8>> for ConfidentialBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for ConfidentialBytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for ConfidentialBytes {
+    fn from(bytes: &[u8; N]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+// Takes over the buffer only when `bytes` is its sole owner. Shared and static buffers are
+// copied instead, and the original is left as is, not wiped.
+impl From<Bytes> for ConfidentialBytes {
+    fn from(bytes: Bytes) -> Self {
+        Self(Vec::from(bytes.0))
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl alloy_rlp::Encodable for ConfidentialBytes {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.0.as_slice().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        self.0.as_slice().length()
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl alloy_rlp::Decodable for ConfidentialBytes {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let bytes = alloy_rlp::Header::decode_bytes(buf, false)?;
+        Ok(Self::from(bytes))
+    }
+}
+
+// Hex-encoded like `Bytes`, written straight to the serializer without an intermediate string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConfidentialBytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Hex<'a>(&'a [u8]);
+
+        impl fmt::Display for Hex<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("0x")?;
+                self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+        }
+
+        serializer.collect_str(&Hex(&self.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ConfidentialBytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Bytes::deserialize(deserializer).map(Self::from)
+    }
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use alloy_rlp::{Decodable, Encodable};
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let cinputs = ConfidentialBytes::from(b"private order flow");
+        assert_eq!(format!("{cinputs:?}"), "ConfidentialBytes(<redacted>)");
+        assert_eq!(format!("{cinputs:#?}"), "ConfidentialBytes(<redacted>)");
+        assert_eq!(cinputs.to_string(), "<redacted>");
+        assert_eq!(cinputs.expose(), b"private order flow");
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut cinputs = ConfidentialBytes::from(b"private order flow");
+        cinputs.zeroize();
+        assert!(cinputs.is_empty());
+    }
+
+    // Reads the buffer the drop test tracks as it is freed, the only time it can still be seen.
+    mod checked_alloc {
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+        pub(super) static TRACKED: AtomicPtr<u8> = AtomicPtr::new(std::ptr::null_mut());
+        pub(super) static FREED_ZEROED: AtomicBool = AtomicBool::new(false);
+
+        struct CheckedAlloc;
+
+        unsafe impl GlobalAlloc for CheckedAlloc {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                let tracked = TRACKED.compare_exchange(ptr, std::ptr::null_mut(), Ordering::SeqCst, Ordering::SeqCst);
+                if tracked.is_ok() {
+                    let zeroed = std::slice::from_raw_parts(ptr, layout.size()).iter().all(|byte| *byte == 0);
+                    FREED_ZEROED.store(zeroed, Ordering::SeqCst);
+                }
+                System.dealloc(ptr, layout)
+            }
+        }
+
+        #[global_allocator]
+        static ALLOC: CheckedAlloc = CheckedAlloc;
+    }
+
+    #[test]
+    fn test_zeroize_on_drop() {
+        use std::sync::atomic::Ordering;
+        use checked_alloc::{FREED_ZEROED, TRACKED};
+
+        let cinputs = ConfidentialBytes::from(b"private order flow");
+        TRACKED.store(cinputs.expose().as_ptr() as *mut u8, Ordering::SeqCst);
+        drop(cinputs);
+        assert!(TRACKED.load(Ordering::SeqCst).is_null(), "buffer was not freed");
+        assert!(FREED_ZEROED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_encodes_like_bytes() {
+        let bytes = Bytes::from(vec![0xab; 100]);
+        let cinputs = ConfidentialBytes::from(bytes.clone());
+
+        let mut encoded = Vec::new();
+        cinputs.encode(&mut encoded);
+        assert_eq!(encoded, alloy_rlp::encode(&bytes));
+        assert_eq!(cinputs.length(), encoded.len());
+        assert_eq!(ConfidentialBytes::decode(&mut encoded.as_slice()).unwrap(), cinputs);
+
+        let json = serde_json::to_string(&cinputs).unwrap();
+        assert_eq!(json, serde_json::to_string(&bytes).unwrap());
+        assert_eq!(serde_json::from_str::<ConfidentialBytes>(&json).unwrap(), cinputs);
+        assert_eq!(serde_json::to_string(&ConfidentialBytes::new()).unwrap(), r#""0x""#);
+        assert_eq!(cinputs.hash(), keccak256(&bytes));
+    }
+
+}
@@ -1,58 +1,59 @@
-use serde::{Deserialize, Serialize};
-use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
-use eyre::{Result, eyre};
-use alloy::{
-    primitives::{self, Address, Bytes, FixedBytes, U256, Signature}, 
-    rpc::types::eth::TransactionRequest,
-    serde as alloy_serde,
-};
+use alloy_primitives::{self as primitives, Address, Bytes, ChainId, FixedBytes, B256, U256, Signature};
+#[cfg(feature = "rlp")]
+use alloy_rlp::{Decodable, Encodable, Header};
+#[cfg(feature = "consensus")]
+use alloy_primitives::TxKind;
+#[cfg(feature = "consensus")]
+use alloy_consensus::{SignableTransaction, Signed, Transaction};
+#[cfg(feature = "consensus")]
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+#[cfg(feature = "rpc")]
+use alloy_rpc_types::eth::TransactionRequest;
+#[cfg(feature = "rpc")]
+use super::crequest::ConfidentialComputeRequest;
+#[cfg(feature = "rlp")]
+use super::crequest::{encode_with_prefix, CRequestHashParams};
+use super::error::{Result, SuaveTypesError};
+#[cfg(feature = "rpc")]
+use super::error::reject_unsupported;
+#[cfg(feature = "rlp")]
+use super::hash::{RecordSigningHash, RequestHash};
+use super::spec::SuaveSpec;
 
 
 pub const EMPTY_BYTES_HASH: FixedBytes<32> = FixedBytes([
     197,210,70,1,134,247,35,60,146,126,125,178,220,199,3,192,229,0,182,83,202,130,39,59,123,250,216,4,93,133,164,112
 ]);
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+/// Serializes to the 0x42 transaction JSON of suave-geth.
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ConfidentialComputeRecord {
-    #[serde(with = "alloy_serde::num::u64_hex_opt")]
     pub nonce: Option<u64>,
     pub to: Address,
-    #[serde(with = "alloy_serde::num::u128_hex_or_decimal_opt")]
     pub gas: Option<u128>,
-    #[serde(with = "alloy_serde::num::u128_hex_or_decimal_opt")]
     pub gas_price: Option<u128>,
     pub value: U256,
     pub input: Bytes,
     pub kettle_address: Option<Address>,
-    #[serde(with = "alloy_serde::num::u64_hex_opt")]
     pub chain_id: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub confidential_inputs_hash: Option<FixedBytes<32>>,
-    #[serde(default)]
     pub is_eip712: bool,
-    #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub signature: Option<Signature>,
-    #[serde(skip)]
     pub from: Option<Address>,
 }
 
 impl ConfidentialComputeRecord {
     pub const TYPE: u8 = 0x42;
 
+    /// Builds a record from an Ethereum request, keeping `from`. Fails on contract creations and
+    /// lists every field a record can't carry: EIP-1559 fees, access lists and blobs.
+    #[cfg(feature = "rpc")]
     pub fn from_tx_request(
         tx_req: TransactionRequest, 
         kettle_address: Address, 
     ) -> Result<Self> {
-        let mut crecord = Self::default();
-        crecord.input = tx_req.input.input.unwrap_or_default();
-        crecord.value = tx_req.value.unwrap_or_default();
-        crecord.to = tx_req.to.unwrap_or_default();
+        let mut crecord = record_from_tx_request(tx_req)?;
         crecord.kettle_address = Some(kettle_address);
-        crecord.gas_price = tx_req.gas_price;
-        crecord.chain_id = tx_req.chain_id;
-        crecord.nonce = tx_req.nonce;
-        crecord.gas = tx_req.gas;
 
         Ok(crecord)
     }
@@ -73,27 +74,392 @@ impl ConfidentialComputeRecord {
         self.signature = Some(signature);
     }
 
-}
+    /// Encoding rules of the record's chain, the latest ones if the chain id isn't set.
+    pub fn spec(&self) -> SuaveSpec {
+        self.chain_id.map_or(SuaveSpec::LATEST, SuaveSpec::from_chain_id)
+    }
+
+    /// Hash signed by the sender, either the 0x42-prefixed RLP hash or the EIP-712 hash.
+    #[cfg(feature = "rlp")]
+    pub fn signature_hash(&self) -> Result<RecordSigningHash> {
+        self.spec().record_signing_hash(&CRequestHashParams::try_from(self)?, self.is_eip712)
+    }
+
+    /// Transaction hash of the confidential compute request carrying this record, fails if it isn't signed.
+    #[cfg(feature = "rlp")]
+    pub fn request_hash(&self) -> Result<RequestHash> {
+        Ok(primitives::keccak256(self.rlp_encode()?).into())
+    }
+
+    /// Recovers the sender from the record signature.
+    #[cfg(all(feature = "rlp", feature = "k256"))]
+    pub fn recover_signer(&self) -> Result<Address> {
+        let sig = self.signature.ok_or(SuaveTypesError::MissingField("signature"))?;
+        Ok(sig.recover_address_from_prehash(self.signature_hash()?.as_b256())?)
+    }
+
+    /// EIP-2718 encoding of the signed record as a 0x42 transaction.
+    #[cfg(feature = "rlp")]
+    pub fn rlp_encode(&self) -> Result<Bytes> {
+        let rlp_encoded = encode_with_prefix(
+            ConfidentialComputeRecord::TYPE, 
+            CRecordRLP::try_from(self)?
+        );
 
+        Ok(rlp_encoded)
+    }
 
-#[derive(Debug, RlpEncodable, RlpDecodable, PartialEq)]
-pub struct CRecordRLP {
+}
+
+/// Confidential compute record with every field needed to hash, sign and encode it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsignedConfidentialComputeRecord {
     nonce: u64,
+    gas: u64,
     gas_price: u128,
-    gas: u128,
     to: Address,
     value: U256,
     input: Bytes,
     kettle_address: Address,
-    confidential_inputs_hash: FixedBytes<32>,
+    chain_id: ChainId,
+    confidential_inputs_hash: B256,
     is_eip712: bool,
-    chain_id: u64,
-    v: u8,
-    r: U256,
-    s: U256,
 }
 
-impl CRecordRLP {
+impl UnsignedConfidentialComputeRecord {
+
+    pub fn kettle_address(&self) -> Address {
+        self.kettle_address
+    }
+
+    pub fn confidential_inputs_hash(&self) -> B256 {
+        self.confidential_inputs_hash
+    }
+
+    pub fn is_eip712(&self) -> bool {
+        self.is_eip712
+    }
+
+    /// Sign the record as EIP-712 typed data instead of the 0x42-prefixed RLP hash.
+    /// Fails on networks whose records don't carry the flag.
+    pub fn set_eip712(&mut self, is_eip712: bool) -> Result<()> {
+        self.spec().check_eip712(is_eip712)?;
+        self.is_eip712 = is_eip712;
+        Ok(())
+    }
+
+    pub fn spec(&self) -> SuaveSpec {
+        SuaveSpec::from_chain_id(self.chain_id)
+    }
+
+    /// Hash the sender signs, same as `SignableTransaction::signature_hash`.
+    /// Fails if `is_eip712` is set on a network without EIP-712 records, see `set_chain_id`.
+    #[cfg(feature = "rlp")]
+    pub fn record_signing_hash(&self) -> Result<RecordSigningHash> {
+        self.spec().record_signing_hash(&self.hash_params(), self.is_eip712)
+    }
+
+    /// Transaction hash of the record once signed with `signature`.
+    #[cfg(feature = "rlp")]
+    pub fn request_hash(&self, signature: Signature) -> RequestHash {
+        self.spec().request_hash(self.hash_params(), self.is_eip712, self.chain_id, signature)
+    }
+
+    #[cfg(feature = "rlp")]
+    pub(crate) fn hash_params(&self) -> CRequestHashParams<'_> {
+        CRequestHashParams {
+            kettle_address: self.kettle_address,
+            confidential_inputs_hash: self.confidential_inputs_hash,
+            nonce: self.nonce,
+            gas_price: self.gas_price,
+            gas: self.gas,
+            to: self.to,
+            value: self.value,
+            input: &self.input,
+        }
+    }
+
+    #[cfg(feature = "rlp")]
+    pub(crate) fn record_rlp(&self, signature: Signature) -> CRecordRLP<&Bytes> {
+        self.spec().record_rlp(self.hash_params(), self.is_eip712, self.chain_id, signature)
+    }
+
+    pub(crate) fn record(&self, signature: Option<Signature>) -> ConfidentialComputeRecord {
+        ConfidentialComputeRecord {
+            nonce: Some(self.nonce),
+            to: self.to,
+            gas: Some(self.gas as u128),
+            gas_price: Some(self.gas_price),
+            value: self.value,
+            input: self.input.clone(),
+            kettle_address: Some(self.kettle_address),
+            chain_id: Some(self.chain_id),
+            confidential_inputs_hash: Some(self.confidential_inputs_hash),
+            is_eip712: self.is_eip712,
+            signature,
+            from: None,
+        }
+    }
+
+}
+
+#[cfg(feature = "consensus")]
+impl Transaction for UnsignedConfidentialComputeRecord {
+
+    fn input(&self) -> &[u8] {
+        self.input.as_ref()
+    }
+
+    fn to(&self) -> TxKind {
+        TxKind::Call(self.to)
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        Some(self.chain_id)
+    }
+
+    fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn gas_limit(&self) -> u128 {
+        self.gas as u128
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        Some(self.gas_price)
+    }
+
+}
+
+#[cfg(feature = "consensus")]
+impl SignableTransaction<Signature> for UnsignedConfidentialComputeRecord {
+
+    /// Keeps `is_eip712`, so moving an EIP-712 record to a network without EIP-712 records
+    /// makes `record_signing_hash` and decoding fail rather than signing it differently.
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.chain_id = chain_id;
+    }
+
+    fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.spec().encode_record_for_signing(&self.hash_params(), self.is_eip712, out);
+    }
+
+    fn payload_len_for_signature(&self) -> usize {
+        self.spec().record_signing_payload_len(&self.hash_params(), self.is_eip712)
+    }
+
+    fn into_signed(self, signature: Signature) -> Signed<Self, Signature> where Self: Sized {
+        let signature = signature.with_parity_bool();
+        let hash = self.request_hash(signature).into();
+        Signed::new_unchecked(self, signature, hash)
+    }
+
+}
+
+/// Ignores the signature and `from`, a missing confidential inputs hash commits to empty inputs.
+impl TryFrom<ConfidentialComputeRecord> for UnsignedConfidentialComputeRecord {
+    type Error = SuaveTypesError;
+
+    fn try_from(record: ConfidentialComputeRecord) -> Result<Self> {
+        let nonce = record.nonce.ok_or(SuaveTypesError::MissingField("nonce"))?;
+        let gas = record.gas.ok_or(SuaveTypesError::MissingField("gas"))?;
+        let gas_price = record.gas_price.ok_or(SuaveTypesError::MissingField("gas_price"))?;
+        let chain_id = record.chain_id.ok_or(SuaveTypesError::MissingField("chain_id"))?;
+        let kettle_address = record.kettle_address.ok_or(SuaveTypesError::MissingField("kettle_address"))?;
+        let gas = u64::try_from(gas).map_err(|_| SuaveTypesError::InvalidField("gas"))?;
+        SuaveSpec::from_chain_id(chain_id).check_eip712(record.is_eip712)?;
+
+        Ok(Self {
+            nonce,
+            gas,
+            gas_price,
+            to: record.to,
+            value: record.value,
+            input: record.input,
+            kettle_address,
+            chain_id,
+            confidential_inputs_hash: record.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH),
+            is_eip712: record.is_eip712,
+        })
+    }
+}
+
+impl From<UnsignedConfidentialComputeRecord> for ConfidentialComputeRecord {
+    fn from(tx: UnsignedConfidentialComputeRecord) -> Self {
+        tx.record(None)
+    }
+}
+
+/// A confidential compute record with its signature and all encoded fields present.
+#[cfg(feature = "rlp")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedConfidentialComputeRecord {
+    tx: UnsignedConfidentialComputeRecord,
+    signature: Signature,
+    hash: RequestHash,
+}
+
+#[cfg(feature = "rlp")]
+impl SignedConfidentialComputeRecord {
+
+    /// Attaches the sender's signature, same as `SignableTransaction::into_signed`.
+    pub fn new(tx: UnsignedConfidentialComputeRecord, signature: Signature) -> Self {
+        let signature = signature.with_parity_bool();
+        let hash = tx.request_hash(signature);
+        Self { tx, signature, hash }
+    }
+
+    pub fn tx(&self) -> &UnsignedConfidentialComputeRecord {
+        &self.tx
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Hash of the 0x42-encoded record.
+    pub fn hash(&self) -> RequestHash {
+        self.hash
+    }
+
+    #[cfg(feature = "k256")]
+    pub fn recover_signer(&self) -> Result<Address> {
+        Ok(self.signature.recover_address_from_prehash(self.tx.record_signing_hash()?.as_b256())?)
+    }
+
+    pub fn strip_signature(self) -> UnsignedConfidentialComputeRecord {
+        self.tx
+    }
+
+    /// Converts back into a record with the signature, and the recovered sender when `k256` is enabled.
+    pub fn into_record(self) -> ConfidentialComputeRecord {
+        let record = self.tx.record(Some(self.signature));
+        #[cfg(feature = "k256")]
+        let record = ConfidentialComputeRecord { from: self.recover_signer().ok(), ..record };
+        record
+    }
+
+}
+
+#[cfg(feature = "rlp")]
+impl TryFrom<ConfidentialComputeRecord> for SignedConfidentialComputeRecord {
+    type Error = SuaveTypesError;
+
+    fn try_from(record: ConfidentialComputeRecord) -> Result<Self> {
+        let signature = record.signature.ok_or(SuaveTypesError::MissingField("signature"))?;
+        let tx = UnsignedConfidentialComputeRecord::try_from(record)?;
+        Ok(Self::new(tx, signature))
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl From<SignedConfidentialComputeRecord> for ConfidentialComputeRecord {
+    fn from(signed: SignedConfidentialComputeRecord) -> Self {
+        signed.into_record()
+    }
+}
+
+#[cfg(feature = "consensus")]
+impl From<Signed<UnsignedConfidentialComputeRecord, Signature>> for SignedConfidentialComputeRecord {
+    fn from(signed: Signed<UnsignedConfidentialComputeRecord, Signature>) -> Self {
+        let hash = RequestHash::from(*signed.hash());
+        let (tx, signature, _) = signed.into_parts();
+        Self { tx, signature, hash }
+    }
+}
+
+#[cfg(feature = "consensus")]
+impl Encodable2718 for SignedConfidentialComputeRecord {
+    fn type_flag(&self) -> Option<u8> {
+        Some(ConfidentialComputeRecord::TYPE)
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.tx.record_rlp(self.signature).length() + 1
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        out.put_u8(ConfidentialComputeRecord::TYPE);
+        self.tx.record_rlp(self.signature).encode(out);
+    }
+}
+
+/// Fails if the sender can't be recovered from the signature; [`SignedConfidentialComputeRecord::into_record`] sets `from` to it.
+#[cfg(feature = "consensus")]
+impl Decodable2718 for SignedConfidentialComputeRecord {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match ty {
+            ConfidentialComputeRecord::TYPE => {
+                let crecord_rlp = CRecordRLP::decode(buf)?;
+                Ok(Self::try_from(ConfidentialComputeRecord::try_from(crecord_rlp)?)?)
+            }
+            _ => Err(SuaveTypesError::UnexpectedTxType(ty).into())
+        }
+    }
+
+    fn fallback_decode(_buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Err(alloy_rlp::Error::Custom("Only ConfidentialComputeRecord"))
+    }
+}
+
+
+#[cfg(feature = "rpc")]
+pub(crate) fn record_from_tx_request(tx_req: TransactionRequest) -> Result<ConfidentialComputeRecord> {
+    match tx_req.transaction_type {
+        None | Some(0) | Some(ConfidentialComputeRequest::TYPE) => {}
+        Some(ty) => return Err(SuaveTypesError::UnexpectedTxType(ty)),
+    }
+    // Empty lists are what some builders default to, and carry nothing.
+    reject_unsupported([
+        ("max_fee_per_gas", tx_req.max_fee_per_gas.is_some()),
+        ("max_priority_fee_per_gas", tx_req.max_priority_fee_per_gas.is_some()),
+        ("max_fee_per_blob_gas", tx_req.max_fee_per_blob_gas.is_some()),
+        ("access_list", tx_req.access_list.as_ref().is_some_and(|list| !list.0.is_empty())),
+        ("blob_versioned_hashes", tx_req.blob_versioned_hashes.as_ref().is_some_and(|hashes| !hashes.is_empty())),
+        ("sidecar", tx_req.sidecar.is_some()),
+    ])?;
+
+    Ok(ConfidentialComputeRecord {
+        nonce: tx_req.nonce,
+        to: tx_req.to.ok_or(SuaveTypesError::MissingField("to"))?,
+        gas: tx_req.gas,
+        gas_price: tx_req.gas_price,
+        value: tx_req.value.unwrap_or_default(),
+        input: tx_req.input.input().cloned().unwrap_or_default(),
+        chain_id: tx_req.chain_id,
+        from: tx_req.from,
+        ..ConfidentialComputeRecord::default()
+    })
+}
+
+/// RLP layout of a signed record. Decodes into owned `Bytes` and encodes from `&Bytes`
+/// borrowed from the record, so encoding never copies the calldata.
+/// `is_eip712` is `None` on networks whose records don't carry the flag.
+#[cfg(feature = "rlp")]
+#[derive(Debug, PartialEq)]
+pub struct CRecordRLP<B = Bytes> {
+    pub(crate) nonce: u64,
+    pub(crate) gas_price: u128,
+    pub(crate) gas: u128,
+    pub(crate) to: Address,
+    pub(crate) value: U256,
+    pub(crate) input: B,
+    pub(crate) kettle_address: Address,
+    pub(crate) confidential_inputs_hash: FixedBytes<32>,
+    pub(crate) is_eip712: Option<bool>,
+    pub(crate) chain_id: u64,
+    pub(crate) v: u8,
+    pub(crate) r: U256,
+    pub(crate) s: U256,
+}
+
+#[cfg(feature = "rlp")]
+impl<B: Encodable> CRecordRLP<B> {
     pub fn fields_len(&self) -> usize {
         let mut len = 0;
         len += self.nonce.length();
@@ -101,9 +467,12 @@ impl CRecordRLP {
         len += self.gas.length();
         len += self.to.length();
         len += self.value.length();
-        len += self.input.0.length();
+        len += self.input.length();
         len += self.kettle_address.length();
         len += self.confidential_inputs_hash.length();
+        if let Some(is_eip712) = self.is_eip712 {
+            len += is_eip712.length();
+        }
         len += self.chain_id.length();
         len += self.v.length();
         len += self.r.length();
@@ -112,76 +481,168 @@ impl CRecordRLP {
     }
 }
 
-impl TryFrom<&ConfidentialComputeRecord> for CRecordRLP {
-    type Error = eyre::Error; // todo: implement custom errors
+#[cfg(feature = "rlp")]
+impl<B: Encodable> Encodable for CRecordRLP<B> {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.nonce.encode(out);
+        self.gas_price.encode(out);
+        self.gas.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.input.encode(out);
+        self.kettle_address.encode(out);
+        self.confidential_inputs_hash.encode(out);
+        if let Some(is_eip712) = self.is_eip712 {
+            is_eip712.encode(out);
+        }
+        self.chain_id.encode(out);
+        self.v.encode(out);
+        self.r.encode(out);
+        self.s.encode(out);
+    }
 
-    fn try_from(ccr: &ConfidentialComputeRecord) -> Result<Self> {
-        let sig = ccr.signature
-            .expect("Missing signature field");
-        let (v, r, s) = signature_to_vrs(sig);
-        let cinputs_hash = ccr.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH);
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
 
-        Ok(Self {
-            nonce: ccr.nonce.ok_or_else(|| eyre!("Missing nonce field"))?,
-            gas_price: ccr.gas_price.ok_or_else(|| eyre!("Missing gas price field"))?,
-            gas: ccr.gas.ok_or_else(|| eyre!("Missing gas field"))?,
-            to: ccr.to,
-            value: ccr.value,
-            input: ccr.input.clone(),
-            kettle_address: ccr.kettle_address.ok_or_else(|| eyre!("Missing kettle address field"))?,
-            confidential_inputs_hash: cinputs_hash,
-            is_eip712: ccr.is_eip712,
-            chain_id: ccr.chain_id.ok_or_else(|| eyre!("Missing chain id field"))?,
-            v, r, s
-        })
+#[cfg(feature = "rlp")]
+impl<B: Decodable> Decodable for CRecordRLP<B> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+        let payload = &mut payload;
+
+        let nonce = Decodable::decode(payload)?;
+        let gas_price = Decodable::decode(payload)?;
+        let gas = Decodable::decode(payload)?;
+        let to = Decodable::decode(payload)?;
+        let value = Decodable::decode(payload)?;
+        let input = Decodable::decode(payload)?;
+        let kettle_address = Decodable::decode(payload)?;
+        let confidential_inputs_hash = Decodable::decode(payload)?;
+        // Records without the flag end with chain_id, v, r and s.
+        let is_eip712 = match count_items(payload)? {
+            5 => Some(Decodable::decode(payload)?),
+            4 => None,
+            _ => return Err(alloy_rlp::Error::Custom("unexpected record field count")),
+        };
+        let record = Self {
+            nonce,
+            gas_price,
+            gas,
+            to,
+            value,
+            input,
+            kettle_address,
+            confidential_inputs_hash,
+            is_eip712,
+            chain_id: Decodable::decode(payload)?,
+            v: Decodable::decode(payload)?,
+            r: Decodable::decode(payload)?,
+            s: Decodable::decode(payload)?,
+        };
+        *buf = rest;
+        Ok(record)
     }
 }
 
-impl Into<ConfidentialComputeRecord> for CRecordRLP {
-    fn into(self) -> ConfidentialComputeRecord {
-        let sig = Signature::from_rs_and_parity(self.r, self.s, self.v as u64)
-            .expect("Invalid signature");
-        ConfidentialComputeRecord {
-            nonce: Some(self.nonce),
-            gas_price: Some(self.gas_price),
-            gas: Some(self.gas),
-            to: self.to,
-            value: self.value,
-            input: self.input,
-            kettle_address: Some(self.kettle_address),
-            chain_id: Some(self.chain_id),
-            confidential_inputs_hash: Some(self.confidential_inputs_hash),
-            is_eip712: self.is_eip712,
-            signature: Some(sig),
-            from: None, // todo: retrieve from signature and prehash
+#[cfg(feature = "rlp")]
+fn count_items(mut payload: &[u8]) -> alloy_rlp::Result<usize> {
+    let mut count = 0;
+    while !payload.is_empty() {
+        let header = Header::decode(&mut payload)?;
+        if payload.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort);
         }
+        payload = &payload[header.payload_length..];
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(feature = "rlp")]
+impl<'a> TryFrom<&'a ConfidentialComputeRecord> for CRecordRLP<&'a Bytes> {
+    type Error = SuaveTypesError;
+
+    fn try_from(ccr: &'a ConfidentialComputeRecord) -> Result<Self> {
+        let signature = ccr.signature.ok_or(SuaveTypesError::MissingField("signature"))?;
+        let params = CRequestHashParams::try_from(ccr)?;
+        let chain_id = ccr.chain_id.ok_or(SuaveTypesError::MissingField("chain_id"))?;
+        let spec = SuaveSpec::from_chain_id(chain_id);
+        spec.check_eip712(ccr.is_eip712)?;
+        Ok(spec.record_rlp(params, ccr.is_eip712, chain_id, signature))
+    }
+}
+
+/// The sender isn't encoded: with `k256` it is recovered from the signature, and left unset if
+/// it can't be or without `k256`.
+#[cfg(feature = "rlp")]
+impl TryFrom<CRecordRLP> for ConfidentialComputeRecord {
+    type Error = SuaveTypesError;
+
+    fn try_from(rlp: CRecordRLP) -> Result<Self> {
+        if rlp.is_eip712.is_some() != SuaveSpec::from_chain_id(rlp.chain_id).eip712_records {
+            return Err(SuaveTypesError::InvalidField("is_eip712"));
+        }
+        let sig = Signature::from_rs_and_parity(rlp.r, rlp.s, rlp.v as u64)?;
+        let record = ConfidentialComputeRecord {
+            nonce: Some(rlp.nonce),
+            gas_price: Some(rlp.gas_price),
+            gas: Some(rlp.gas),
+            to: rlp.to,
+            value: rlp.value,
+            input: rlp.input,
+            kettle_address: Some(rlp.kettle_address),
+            chain_id: Some(rlp.chain_id),
+            confidential_inputs_hash: Some(rlp.confidential_inputs_hash),
+            is_eip712: rlp.is_eip712.unwrap_or_default(),
+            signature: Some(sig),
+            from: None,
+        };
+        #[cfg(feature = "k256")]
+        let record = ConfidentialComputeRecord { from: record.recover_signer().ok(), ..record };
+        Ok(record)
     }
 
 }
 
+#[cfg(feature = "rlp")]
 pub(crate) fn signature_to_vrs(sig: Signature) -> (u8, U256, U256) {
-    let v = sig.v().recid().to_byte();
+    let v = sig.v().y_parity_byte();
     let r = sig.r();
     let s = sig.s();
     (v, r, s)
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rpc"))]
 mod tests {
     use std::str::FromStr;
 
     use super::*;
     use alloy::{
-        network::TransactionBuilder, 
+        network::{TransactionBuilder, TxSigner}, 
         rpc::types::eth::TransactionRequest, 
+        signers::wallet::LocalWallet,
     };
+    use crate::ConfidentialComputeRequest;
+    use proptest::prelude::*;
+    use crate::test_utils::{arb_signed_record, devnet_kettle, devnet_record};
 
 
     #[test]
     fn test_ccr_rlp_encode() -> Result<()> {
         let chain_id = 0x067932;
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let to_add = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
         let input = Bytes::from_str("0x236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000780675d71ebe3d3ef05fae379063071147dd3aee0000000000000000000000000000000000000000000000000000000000000000").unwrap();
         let tx = TransactionRequest::default()
@@ -211,14 +672,19 @@ mod tests {
     #[test]
     fn test_ccr_rlp_encode_missing_fields() -> Result<()> {
         let chain_id = 0x067932;
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let tx = TransactionRequest::default()
             .gas_limit(0x0f4240)
             .with_chain_id(chain_id);
-        
+
+        let err = ConfidentialComputeRecord::from_tx_request(tx.clone(), kettle_address).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("to")));
+
+        let to = Address::repeat_byte(0x78);
+        let tx = tx.to(to);
         let cc_record = ConfidentialComputeRecord::from_tx_request(tx.clone(), kettle_address)?;
         assert_eq!(cc_record.kettle_address, Some(kettle_address));
-        assert_eq!(cc_record.to, Address::ZERO);
+        assert_eq!(cc_record.to, to);
         assert_eq!(cc_record.gas, tx.gas);
         assert_eq!(cc_record.gas_price, None);
         assert_eq!(cc_record.chain_id, Some(chain_id));
@@ -231,4 +697,200 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_crecord_encode_decode_2718() -> Result<()> {
+        let cc_record = devnet_record();
+        let expected_rlp_encoded = Bytes::from_str("0x42f9016422843b9aca00830f424094780675d71ebe3d3ef05fae379063071147dd3aee80b8c4236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000780675d71ebe3d3ef05fae379063071147dd3aee0000000000000000000000000000000000000000000000000000000000000000947d83e42b214b75bf1f3e57adc3415da573d97bffa089ee438ca379ac86b0478517d43a6a9e078cf51543acac0facd68aff313e2ff1808306793280a01567c31c4bebcd1061edbaf22dd73fd40ff30f9a3ba4525037f23b2dc61e3473a02dce69262794a499d525c5d58edde33e06a5847b4d321d396b743700a2fd71a8").unwrap();
+        assert_eq!(cc_record.rlp_encode()?, expected_rlp_encoded);
+
+        let signed_record = SignedConfidentialComputeRecord::try_from(cc_record.clone())?;
+        assert_eq!(signed_record.hash().into_b256(), primitives::keccak256(&expected_rlp_encoded));
+        assert_eq!(signed_record.encode_2718_len(), expected_rlp_encoded.len());
+        let mut encoded = Vec::new();
+        signed_record.encode_2718(&mut encoded);
+        assert_eq!(encoded, expected_rlp_encoded.to_vec());
+
+        let decoded = SignedConfidentialComputeRecord::decode_2718(&mut encoded.as_slice()).unwrap();
+        let signer = Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a").unwrap();
+        assert_eq!(decoded.recover_signer()?, signer);
+        assert_eq!(decoded.hash(), signed_record.hash());
+        assert_eq!(decoded.into_record(), ConfidentialComputeRecord { from: Some(signer), ..cc_record.clone() });
+
+        let record_rlp = CRecordRLP::decode(&mut &encoded[1..]).unwrap();
+        assert_eq!(ConfidentialComputeRecord::try_from(record_rlp)?, ConfidentialComputeRecord { from: Some(signer), ..cc_record });
+
+        encoded[0] = ConfidentialComputeRequest::TYPE;
+        let err = SignedConfidentialComputeRecord::decode_2718(&mut encoded.as_slice()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("unexpected tx type"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_crecord_sign() -> Result<()> {
+        let mut unsigned = UnsignedConfidentialComputeRecord::try_from(devnet_record())?;
+        let wallet: LocalWallet = "0x1111111111111111111111111111111111111111111111111111111111111111".parse().unwrap();
+        let sig = wallet.sign_transaction(&mut unsigned).await.unwrap();
+        let expected_sig = devnet_record().signature.unwrap();
+        assert_eq!((sig.r(), sig.s()), (expected_sig.r(), expected_sig.s()));
+        assert_eq!(unsigned.signature_hash(), devnet_record().signature_hash()?.into_b256());
+
+        let signed: SignedConfidentialComputeRecord = unsigned.into_signed(sig).into();
+        assert_eq!(signed.recover_signer()?, wallet.address());
+        assert_eq!(signed.hash(), devnet_record().request_hash()?);
+        assert_eq!(signed.into_record(), ConfidentialComputeRecord { from: Some(wallet.address()), ..devnet_record() });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_record_required_fields() {
+        let mut cc_record = ConfidentialComputeRecord::default();
+        let err = UnsignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("nonce")));
+
+        cc_record.nonce = Some(1);
+        cc_record.gas = Some(u64::MAX as u128 + 1);
+        cc_record.gas_price = Some(1);
+        cc_record.chain_id = Some(SuaveSpec::RIGIL_CHAIN_ID);
+        let err = UnsignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("kettle_address")));
+
+        cc_record.kettle_address = Some(Address::ZERO);
+        let err = SignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("signature")));
+        let err = UnsignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("gas")));
+
+        cc_record.gas = Some(21000);
+        cc_record.is_eip712 = true;
+        let err = UnsignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+
+        cc_record.is_eip712 = false;
+        let unsigned = UnsignedConfidentialComputeRecord::try_from(cc_record.clone()).unwrap();
+        assert_eq!(unsigned.confidential_inputs_hash(), EMPTY_BYTES_HASH);
+        assert_eq!(unsigned.gas_limit(), 21000);
+        assert_eq!(ConfidentialComputeRecord::from(unsigned), ConfidentialComputeRecord { confidential_inputs_hash: Some(EMPTY_BYTES_HASH), ..cc_record });
+    }
+
+    #[test]
+    fn test_crecord_to_crequest() -> Result<()> {
+        let cinputs = Bytes::from_static(b"confidential");
+        let cc_record = ConfidentialComputeRecord {
+            confidential_inputs_hash: Some(primitives::keccak256(&cinputs)),
+            ..devnet_record()
+        };
+
+        let err = ConfidentialComputeRequest::try_from(cc_record.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::ConfidentialInputsHashMismatch { .. }));
+
+        let cc_request = ConfidentialComputeRequest::from_record_with_inputs(cc_record.clone(), cinputs.clone())?;
+        assert_eq!(cc_request.record(), &cc_record);
+        assert_eq!(cc_request.clone().into_parts(), (cc_record.clone(), cinputs.into()));
+        assert_eq!(ConfidentialComputeRecord::from(cc_request), cc_record);
+
+        let cc_record = ConfidentialComputeRecord {
+            confidential_inputs_hash: None,
+            ..devnet_record()
+        };
+        let cc_request = ConfidentialComputeRequest::try_from(cc_record)?;
+        assert_eq!(cc_request.confidential_compute_record.confidential_inputs_hash, Some(EMPTY_BYTES_HASH));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_crecord_rlp_missing_fields() {
+        let mut cc_record = ConfidentialComputeRecord::default();
+        let err = CRecordRLP::try_from(&cc_record).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("signature")));
+
+        let sig = Signature::from_rs_and_parity(U256::from(1), U256::from(1), 0).unwrap();
+        cc_record.set_sig(sig);
+        let err = CRecordRLP::try_from(&cc_record).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("nonce")));
+
+        cc_record.nonce = Some(1);
+        cc_record.gas_price = Some(1);
+        cc_record.gas = Some(21000);
+        let err = CRecordRLP::try_from(&cc_record).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("kettle_address")));
+    }
+
+    #[test]
+    fn test_crecord_rlp_invalid_signature() {
+        let crecord_rlp = CRecordRLP {
+            nonce: 1,
+            gas_price: 1,
+            gas: 21000,
+            to: Address::ZERO,
+            value: U256::ZERO,
+            input: Bytes::new(),
+            kettle_address: Address::ZERO,
+            confidential_inputs_hash: EMPTY_BYTES_HASH,
+            is_eip712: Some(false),
+            chain_id: 1,
+            v: 5,
+            r: U256::from(1),
+            s: U256::from(1),
+        };
+        let err = ConfidentialComputeRecord::try_from(crecord_rlp).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidSignature(_)));
+    }
+
+    #[test]
+    fn test_crecord_rlp_spec_layout() -> Result<()> {
+        let toliman_record = devnet_record();
+        let rigil_record = ConfidentialComputeRecord { chain_id: Some(SuaveSpec::RIGIL_CHAIN_ID), ..devnet_record() };
+
+        let toliman_rlp = CRecordRLP::try_from(&toliman_record)?;
+        let rigil_rlp = CRecordRLP::try_from(&rigil_record)?;
+        assert_eq!(toliman_rlp.is_eip712, Some(false));
+        assert_eq!(rigil_rlp.is_eip712, None);
+
+        for record in [toliman_record, rigil_record.clone()] {
+            let encoded = record.rlp_encode()?;
+            let decoded = CRecordRLP::<Bytes>::decode(&mut &encoded[1..]).unwrap();
+            let from = Some(record.recover_signer()?);
+            assert_eq!(ConfidentialComputeRecord::try_from(decoded)?, ConfidentialComputeRecord { from, ..record });
+        }
+
+        // The layout has to match the spec of the encoded chain id.
+        let mut encoded = Vec::new();
+        CRecordRLP { chain_id: SuaveSpec::TOLIMAN_CHAIN_ID, ..CRecordRLP::try_from(&rigil_record)? }.encode(&mut encoded);
+        let decoded = CRecordRLP::<Bytes>::decode(&mut encoded.as_slice()).unwrap();
+        let err = ConfidentialComputeRecord::try_from(decoded).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+
+        let rigil_eip712 = ConfidentialComputeRecord { is_eip712: true, ..rigil_record };
+        let err = CRecordRLP::try_from(&rigil_eip712).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+        let err = rigil_eip712.signature_hash().unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_encoded_lengths(record in arb_signed_record()) {
+            let crecord_rlp = CRecordRLP::try_from(&record).unwrap();
+            let mut encoded = Vec::new();
+            crecord_rlp.encode(&mut encoded);
+            prop_assert_eq!(crecord_rlp.length(), encoded.len());
+            let header = alloy_rlp::Header::decode(&mut encoded.as_slice()).unwrap();
+            prop_assert_eq!(crecord_rlp.fields_len(), header.payload_length);
+
+            let signed_record = SignedConfidentialComputeRecord::try_from(record).unwrap();
+            let mut encoded = Vec::new();
+            signed_record.tx().encode_for_signing(&mut encoded);
+            prop_assert_eq!(signed_record.tx().payload_len_for_signature(), encoded.len());
+
+            let mut encoded = Vec::new();
+            signed_record.encode_2718(&mut encoded);
+            prop_assert_eq!(signed_record.encode_2718_len(), encoded.len());
+        }
+    }
+
 }
@@ -1,19 +1,41 @@
-use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
-use serde::{Deserialize, Serialize};
-use eyre::{eyre, Result};
-use alloy::{
-    primitives::{self, Address, Bytes, FixedBytes, U256, ChainId, Signature, TxKind}, 
-    consensus::{SignableTransaction, Signed, Transaction}, 
-    eips::eip2718::{Decodable2718, Encodable2718}
-};
-use super::crecord::{ConfidentialComputeRecord, CRecordRLP, EMPTY_BYTES_HASH};
-
-
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[cfg(all(feature = "rlp", not(feature = "std")))]
+use alloc::vec::Vec;
+use alloy_primitives::{Address, Bytes, B256, U256};
+#[cfg(feature = "rlp")]
+use alloy_primitives::Signature;
+#[cfg(feature = "rlp")]
+use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
+#[cfg(feature = "consensus")]
+use alloy_rlp::Decodable;
+use alloy_sol_types::{SolType, SolValue};
+#[cfg(feature = "consensus")]
+use alloy_primitives::{ChainId, TxKind};
+#[cfg(feature = "consensus")]
+use alloy_consensus::{SignableTransaction, Signed, Transaction};
+#[cfg(feature = "consensus")]
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+#[cfg(feature = "rpc")]
+use alloy_rpc_types::eth::TransactionRequest;
+use super::cbytes::ConfidentialBytes;
+use super::crecord::{ConfidentialComputeRecord, UnsignedConfidentialComputeRecord, EMPTY_BYTES_HASH};
+#[cfg(feature = "rlp")]
+use super::crecord::{CRecordRLP, SignedConfidentialComputeRecord};
+#[cfg(feature = "rpc")]
+use super::crecord::record_from_tx_request;
+use super::error::{Result, SuaveTypesError};
+#[cfg(feature = "rpc")]
+use super::error::reject_unsupported;
+#[cfg(feature = "rlp")]
+use super::hash::{RecordSigningHash, RequestHash};
+use super::spec::SuaveSpec;
+
+
+/// Builder for a confidential compute request, with the fields fillers may still have to set.
+/// Serializes to the 0x43 transaction JSON of suave-geth, the record fields next to `confidentialInputs`.
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ConfidentialComputeRequest {
     pub confidential_compute_record: ConfidentialComputeRecord,
-    pub confidential_inputs: Bytes,
+    pub confidential_inputs: ConfidentialBytes,
 }
 
 impl ConfidentialComputeRequest {
@@ -21,17 +43,18 @@ impl ConfidentialComputeRequest {
 
     pub fn new(
         mut confidential_compute_record: ConfidentialComputeRecord, 
-        confidential_inputs: Option<Bytes>,
+        confidential_inputs: Option<ConfidentialBytes>,
     ) -> Self {
         let confidential_inputs = confidential_inputs.unwrap_or_default();
         confidential_compute_record
-            .set_confidential_inputs_hash_from_inputs(&confidential_inputs);
+            .set_confidential_inputs_hash(confidential_inputs.hash());
         Self {
             confidential_compute_record,
             confidential_inputs,
         }
     }
 
+    #[cfg(feature = "rlp")]
     pub fn rlp_encode(&self) -> Result<Bytes> {
         let rlp_encoded = encode_with_prefix(
             ConfidentialComputeRequest::TYPE, 
@@ -54,95 +77,418 @@ impl ConfidentialComputeRequest {
         self.confidential_compute_record.kettle_address = Some(kettle_address);
     }
 
-    pub fn with_confidential_inputs(mut self, confidential_inputs: Bytes) -> Self {
+    pub fn with_confidential_inputs(mut self, confidential_inputs: impl Into<ConfidentialBytes>) -> Self {
         self.set_confidential_inputs(confidential_inputs);
         self
     }
 
-    pub fn set_confidential_inputs(&mut self, confidential_inputs: Bytes) {
+    pub fn set_confidential_inputs(&mut self, confidential_inputs: impl Into<ConfidentialBytes>) {
+        let confidential_inputs = confidential_inputs.into();
         self.confidential_compute_record
-            .set_confidential_inputs_hash_from_inputs(&confidential_inputs);
+            .set_confidential_inputs_hash(confidential_inputs.hash());
         self.confidential_inputs = confidential_inputs;
     }
 
-    pub fn confidential_inputs(&self) -> Bytes {
-        self.confidential_inputs.clone()
+    pub fn confidential_inputs(&self) -> &ConfidentialBytes {
+        &self.confidential_inputs
     }
 
-    fn hash(&self) -> FixedBytes<32> {
-        let rlp_encoded = encode_with_prefix(
-            ConfidentialComputeRecord::TYPE, 
-            CRequestHashParams::try_from(self).unwrap()
-        );
-        let hash = primitives::keccak256(&rlp_encoded);
-        hash
+    /// Sets the confidential inputs to an ABI-encoded Solidity value, such as a `sol!` struct.
+    pub fn with_sol_confidential_inputs<T: SolValue + ?Sized>(mut self, value: &T) -> Self {
+        self.set_sol_confidential_inputs(value);
+        self
+    }
+
+    pub fn set_sol_confidential_inputs<T: SolValue + ?Sized>(&mut self, value: &T) {
+        self.set_confidential_inputs(ConfidentialBytes::abi_encode(value));
+    }
+
+    /// Sets the confidential inputs to `value` serialized as JSON and ABI-encoded as `bytes`.
+    #[cfg(feature = "serde")]
+    pub fn with_json_confidential_inputs<T: serde::Serialize + ?Sized>(mut self, value: &T) -> Result<Self> {
+        self.set_json_confidential_inputs(value)?;
+        Ok(self)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn set_json_confidential_inputs<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.set_confidential_inputs(ConfidentialBytes::json_abi_encode(value)?);
+        Ok(())
+    }
+
+    pub fn decode_confidential_inputs<T>(&self) -> Result<T>
+    where
+        T: SolValue + From<<T::SolType as SolType>::RustType>,
+    {
+        self.confidential_inputs.abi_decode()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn decode_json_confidential_inputs<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        self.confidential_inputs.json_abi_decode()
+    }
+
+    /// Wraps a record without confidential inputs, keeping its signature.
+    /// Fails if the record commits to non-empty confidential inputs.
+    pub fn from_record(confidential_compute_record: ConfidentialComputeRecord) -> Result<Self> {
+        Self::from_record_with_inputs(confidential_compute_record, ConfidentialBytes::new())
+    }
+
+    /// Wraps a record with its confidential inputs, keeping its signature.
+    /// Fails if the inputs don't match the record's confidential inputs hash.
+    pub fn from_record_with_inputs(
+        mut confidential_compute_record: ConfidentialComputeRecord, 
+        confidential_inputs: impl Into<ConfidentialBytes>,
+    ) -> Result<Self> {
+        let confidential_inputs = confidential_inputs.into();
+        let expected = confidential_compute_record.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH);
+        let found = confidential_inputs.hash();
+        if expected != found {
+            return Err(SuaveTypesError::ConfidentialInputsHashMismatch { expected, found });
+        }
+        confidential_compute_record.set_confidential_inputs_hash(found);
+        Ok(Self {
+            confidential_compute_record,
+            confidential_inputs,
+        })
+    }
+
+    pub fn record(&self) -> &ConfidentialComputeRecord {
+        &self.confidential_compute_record
+    }
+
+    /// Drops the confidential inputs, leaving the record that commits to them.
+    pub fn into_record(self) -> ConfidentialComputeRecord {
+        self.confidential_compute_record
+    }
+
+    pub fn into_parts(self) -> (ConfidentialComputeRecord, ConfidentialBytes) {
+        (self.confidential_compute_record, self.confidential_inputs)
+    }
+
+    #[cfg(all(feature = "rlp", feature = "k256"))]
+    pub fn recover_signer(&self) -> Result<Address> {
+        self.confidential_compute_record.recover_signer()
+    }
+
+    /// Transaction hash of the request, fails if the record isn't signed or a field is missing.
+    #[cfg(feature = "rlp")]
+    pub fn request_hash(&self) -> Result<RequestHash> {
+        self.confidential_compute_record.request_hash()
+    }
+
+}
+
+impl From<ConfidentialComputeRequest> for ConfidentialComputeRecord {
+    fn from(ccr: ConfidentialComputeRequest) -> Self {
+        ccr.into_record()
+    }
+}
+
+impl TryFrom<ConfidentialComputeRecord> for ConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(crecord: ConfidentialComputeRecord) -> Result<Self> {
+        Self::from_record(crecord)
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl TryFrom<SignedConfidentialComputeRecord> for ConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(crecord: SignedConfidentialComputeRecord) -> Result<Self> {
+        Self::from_record(crecord.into_record())
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl TryFrom<&ConfidentialComputeRequest> for SignedConfidentialComputeRecord {
+    type Error = SuaveTypesError;
+
+    fn try_from(ccr: &ConfidentialComputeRequest) -> Result<Self> {
+        Self::try_from(ccr.confidential_compute_record.clone())
+    }
+}
+
+/// Converts an Ethereum request, keeping `from`, so that Ethereum builders can be used up to
+/// the kettle address and confidential inputs. Contract creations can't be confidential.
+#[cfg(feature = "rpc")]
+impl TryFrom<TransactionRequest> for ConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(tx_req: TransactionRequest) -> Result<Self> {
+        Ok(Self::new(record_from_tx_request(tx_req)?, None))
+    }
+}
+
+/// Converts back to an Ethereum request, keeping `from`. Fails if the request has a kettle
+/// address, confidential inputs, a signature or EIP-712 signing, which Ethereum requests can't carry.
+#[cfg(feature = "rpc")]
+impl TryFrom<ConfidentialComputeRequest> for TransactionRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(ccr: ConfidentialComputeRequest) -> Result<Self> {
+        let (record, confidential_inputs) = ccr.into_parts();
+        reject_unsupported([
+            ("kettle_address", record.kettle_address.is_some()),
+            ("confidential_inputs", !confidential_inputs.is_empty()),
+            ("confidential_inputs_hash", record.confidential_inputs_hash.is_some_and(|hash| hash != EMPTY_BYTES_HASH)),
+            ("is_eip712", record.is_eip712),
+            ("signature", record.signature.is_some()),
+        ])?;
+
+        Ok(TransactionRequest {
+            from: record.from,
+            to: Some(record.to),
+            gas_price: record.gas_price,
+            gas: record.gas,
+            value: Some(record.value),
+            input: record.input.into(),
+            nonce: record.nonce,
+            chain_id: record.chain_id,
+            ..TransactionRequest::default()
+        })
+    }
+}
+
+/// Confidential compute request with every field needed to hash, sign and encode it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsignedConfidentialComputeRequest {
+    record: UnsignedConfidentialComputeRecord,
+    confidential_inputs: ConfidentialBytes,
+}
+
+impl UnsignedConfidentialComputeRequest {
+
+    /// The record committing to the confidential inputs, which is what the sender signs.
+    pub fn record(&self) -> &UnsignedConfidentialComputeRecord {
+        &self.record
+    }
+
+    pub fn kettle_address(&self) -> Address {
+        self.record.kettle_address()
+    }
+
+    pub fn confidential_inputs(&self) -> &ConfidentialBytes {
+        &self.confidential_inputs
+    }
+
+    pub fn confidential_inputs_hash(&self) -> B256 {
+        self.record.confidential_inputs_hash()
+    }
+
+    pub fn is_eip712(&self) -> bool {
+        self.record.is_eip712()
+    }
+
+    /// Sign the record as EIP-712 typed data instead of the 0x42-prefixed RLP hash.
+    /// Fails on networks whose records don't carry the flag.
+    pub fn set_eip712(&mut self, is_eip712: bool) -> Result<()> {
+        self.record.set_eip712(is_eip712)
+    }
+
+    pub fn spec(&self) -> SuaveSpec {
+        self.record.spec()
+    }
+
+    /// Hash the sender signs, same as `SignableTransaction::signature_hash`.
+    /// Fails if `is_eip712` is set on a network without EIP-712 records.
+    #[cfg(feature = "rlp")]
+    pub fn record_signing_hash(&self) -> Result<RecordSigningHash> {
+        self.record.record_signing_hash()
+    }
+
+    /// Transaction hash of the request once signed with `signature`.
+    #[cfg(feature = "rlp")]
+    pub fn request_hash(&self, signature: Signature) -> RequestHash {
+        self.record.request_hash(signature)
     }
 
 }
 
-impl Transaction for ConfidentialComputeRequest {
+#[cfg(feature = "consensus")]
+impl Transaction for UnsignedConfidentialComputeRequest {
 
     fn input(&self) -> &[u8] {
-        self.confidential_compute_record.input.as_ref()
+        self.record.input()
     }
 
     fn to(&self) -> TxKind {
-        TxKind::Call(self.confidential_compute_record.to)
+        self.record.to()
     }
 
     fn value(&self) -> U256 {
-        self.confidential_compute_record.value
+        self.record.value()
     }
 
     fn chain_id(&self) -> Option<ChainId> {
-        self.confidential_compute_record.chain_id
+        self.record.chain_id()
     }
 
     fn nonce(&self) -> u64 {
-        self.confidential_compute_record.nonce.unwrap_or_default() // todo: temp solution, this cant be default
+        self.record.nonce()
     }
 
     fn gas_limit(&self) -> u128 {
-        self.confidential_compute_record.gas.unwrap_or_default() // todo: temp solution, this cant be default
+        self.record.gas_limit()
     }
 
     fn gas_price(&self) -> Option<u128> {
-        self.confidential_compute_record.gas_price
+        self.record.gas_price()
     }
 
 }
 
-impl SignableTransaction<Signature> for ConfidentialComputeRequest {
+#[cfg(feature = "consensus")]
+impl SignableTransaction<Signature> for UnsignedConfidentialComputeRequest {
 
     fn set_chain_id(&mut self, chain_id: ChainId) {
-        self.confidential_compute_record.chain_id = Some(chain_id);
+        self.record.set_chain_id(chain_id);
     }
 
     fn encode_for_signing(&self, out: &mut dyn alloy_rlp::BufMut) {
-        out.put_u8(ConfidentialComputeRecord::TYPE);
-        CRequestHashParams::try_from(self).unwrap().encode(out);
+        self.record.encode_for_signing(out);
     }
 
     fn payload_len_for_signature(&self) -> usize {
-        let chain_id = self.confidential_compute_record.chain_id.expect("Chain ID not set");
-        CRequestHashParams::try_from(self).unwrap().fields_len() + chain_id as usize + 2
+        self.record.payload_len_for_signature()
     }
 
     fn into_signed(self, signature: Signature) -> Signed<Self, Signature> where Self: Sized {
-        let hash = self.hash().into();
-        Signed::new_unchecked(self, signature.with_parity_bool(), hash)
+        let signature = signature.with_parity_bool();
+        let hash = self.request_hash(signature).into();
+        Signed::new_unchecked(self, signature, hash)
     }
 
 }
 
-impl Decodable2718 for ConfidentialComputeRequest {
+impl TryFrom<ConfidentialComputeRequest> for UnsignedConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(ccr: ConfidentialComputeRequest) -> Result<Self> {
+        let (mut record, confidential_inputs) = ccr.into_parts();
+        let found = confidential_inputs.hash();
+        if let Some(expected) = record.confidential_inputs_hash {
+            if expected != found {
+                return Err(SuaveTypesError::ConfidentialInputsHashMismatch { expected, found });
+            }
+        }
+        record.set_confidential_inputs_hash(found);
+
+        Ok(Self {
+            record: UnsignedConfidentialComputeRecord::try_from(record)?,
+            confidential_inputs,
+        })
+    }
+}
+
+impl From<UnsignedConfidentialComputeRequest> for ConfidentialComputeRequest {
+    fn from(tx: UnsignedConfidentialComputeRequest) -> Self {
+        Self {
+            confidential_compute_record: tx.record.into(),
+            confidential_inputs: tx.confidential_inputs,
+        }
+    }
+}
+
+/// Signed confidential compute request, ready to be sent to a kettle.
+#[cfg(feature = "rlp")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedConfidentialComputeRequest {
+    tx: UnsignedConfidentialComputeRequest,
+    signature: Signature,
+    hash: RequestHash,
+}
+
+#[cfg(feature = "rlp")]
+impl SignedConfidentialComputeRequest {
+
+    /// Attaches the sender's signature, same as `SignableTransaction::into_signed`.
+    pub fn new(tx: UnsignedConfidentialComputeRequest, signature: Signature) -> Self {
+        let signature = signature.with_parity_bool();
+        let hash = tx.request_hash(signature);
+        Self { tx, signature, hash }
+    }
+
+    pub fn tx(&self) -> &UnsignedConfidentialComputeRequest {
+        &self.tx
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn hash(&self) -> RequestHash {
+        self.hash
+    }
+
+    #[cfg(feature = "k256")]
+    pub fn recover_signer(&self) -> Result<Address> {
+        Ok(self.signature.recover_address_from_prehash(self.tx.record_signing_hash()?.as_b256())?)
+    }
+
+    /// EIP-2718 encoding of the request as a 0x43 transaction.
+    pub fn rlp_encode(&self) -> Bytes {
+        encode_with_prefix(ConfidentialComputeRequest::TYPE, CRequestRLP::from(self))
+    }
+
+    pub fn strip_signature(self) -> UnsignedConfidentialComputeRequest {
+        self.tx
+    }
+
+    /// Converts back into a request builder, with the signature and recovered sender on the record.
+    #[cfg(feature = "k256")]
+    pub fn into_request(self) -> ConfidentialComputeRequest {
+        let mut confidential_compute_record = self.tx.record.record(Some(self.signature));
+        confidential_compute_record.from = self.recover_signer().ok();
+        ConfidentialComputeRequest {
+            confidential_compute_record,
+            confidential_inputs: self.tx.confidential_inputs,
+        }
+    }
+
+}
+
+#[cfg(feature = "consensus")]
+impl From<Signed<UnsignedConfidentialComputeRequest, Signature>> for SignedConfidentialComputeRequest {
+    fn from(signed: Signed<UnsignedConfidentialComputeRequest, Signature>) -> Self {
+        let hash = RequestHash::from(*signed.hash());
+        let (tx, signature, _) = signed.into_parts();
+        Self { tx, signature, hash }
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl TryFrom<ConfidentialComputeRequest> for SignedConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    fn try_from(ccr: ConfidentialComputeRequest) -> Result<Self> {
+        let signature = ccr.confidential_compute_record.signature
+            .ok_or(SuaveTypesError::MissingField("signature"))?;
+        let tx = UnsignedConfidentialComputeRequest::try_from(ccr)?;
+        Ok(Self::new(tx, signature))
+    }
+}
+
+#[cfg(all(feature = "rlp", feature = "k256"))]
+impl From<SignedConfidentialComputeRequest> for ConfidentialComputeRequest {
+    fn from(signed: SignedConfidentialComputeRequest) -> Self {
+        signed.into_request()
+    }
+}
+
+/// Fails if the sender can't be recovered from the signature; [`SignedConfidentialComputeRequest::into_request`] sets `from` to it.
+#[cfg(feature = "consensus")]
+impl Decodable2718 for SignedConfidentialComputeRequest {
     fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
         match ty {
             ConfidentialComputeRequest::TYPE => {
-                let crequest_prerlp = CRequestRLP::decode(buf)?;
-                Ok(crequest_prerlp.into())
+                let crequest_rlp = CRequestRLP::decode(buf)?;
+                let ccr = ConfidentialComputeRequest::try_from(crequest_rlp)?;
+                Ok(SignedConfidentialComputeRequest::try_from(ccr)?)
             }
-            _ => Err(alloy_rlp::Error::Custom("Only ConfidentialComputeRequest"))
+            _ => Err(SuaveTypesError::UnexpectedTxType(ty).into())
         }
     }
 
@@ -151,128 +497,135 @@ impl Decodable2718 for ConfidentialComputeRequest {
     }
 }
 
-impl Encodable2718 for ConfidentialComputeRequest {
+#[cfg(feature = "consensus")]
+impl Encodable2718 for SignedConfidentialComputeRequest {
     fn type_flag(&self) -> Option<u8> {
         Some(ConfidentialComputeRequest::TYPE)
     }
 
     fn encode_2718_len(&self) -> usize {
-        CRequestRLP::try_from(self).unwrap().fields_len()
+        CRequestRLP::from(self).length() + 1
     }
 
     fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
         out.put_u8(ConfidentialComputeRequest::TYPE);
-        CRequestRLP::try_from(self).unwrap().encode(out);
+        CRequestRLP::from(self).encode(out);
     }
 }
 
+/// RLP layout of a signed request, borrowing `input` and `confidential_inputs` when encoding.
+#[cfg(feature = "rlp")]
 #[derive(Debug, RlpEncodable, RlpDecodable, PartialEq)]
-struct CRequestRLP {
-    request: CRecordRLP,
-    confidential_inputs: Bytes,
-}
-
-impl CRequestRLP {
-    fn fields_len(&self) -> usize {
-        let mut len = 0;
-        len += self.request.fields_len();
-        len += self.confidential_inputs.length();
-        len
-    }
+struct CRequestRLP<B = Bytes, C = ConfidentialBytes> {
+    request: CRecordRLP<B>,
+    confidential_inputs: C,
 }
 
-impl TryFrom<&ConfidentialComputeRequest> for CRequestRLP {
-    type Error = eyre::Error;
+#[cfg(feature = "rlp")]
+impl<'a> TryFrom<&'a ConfidentialComputeRequest> for CRequestRLP<&'a Bytes, &'a ConfidentialBytes> {
+    type Error = SuaveTypesError;
 
-    fn try_from(ccr: &ConfidentialComputeRequest) -> Result<Self> {
+    fn try_from(ccr: &'a ConfidentialComputeRequest) -> Result<Self> {
         Ok(Self {
             request: (&ccr.confidential_compute_record).try_into()?,
-            confidential_inputs: ccr.confidential_inputs.clone(),
+            confidential_inputs: &ccr.confidential_inputs,
         })
     }
 }
 
-impl Into<ConfidentialComputeRequest> for CRequestRLP {
-    fn into(self) -> ConfidentialComputeRequest {
-        let cc_record: ConfidentialComputeRecord = self.request.into();
-        ConfidentialComputeRequest {
-            confidential_compute_record: cc_record,
-            confidential_inputs: self.confidential_inputs,
+#[cfg(feature = "rlp")]
+impl<'a> From<&'a SignedConfidentialComputeRequest> for CRequestRLP<&'a Bytes, &'a ConfidentialBytes> {
+    fn from(signed: &'a SignedConfidentialComputeRequest) -> Self {
+        Self {
+            request: signed.tx.record.record_rlp(signed.signature),
+            confidential_inputs: &signed.tx.confidential_inputs,
         }
     }
 }
 
-#[derive(Debug, RlpEncodable, PartialEq)]
-struct CRequestHashParams {
-    kettle_address: Address,
-    confidential_inputs_hash: FixedBytes<32>,
-    nonce: u64,
-    gas_price: u128,
-    gas: u128,
-    to: Address,
-    value: U256,
-    input: Bytes,
-}
+#[cfg(all(feature = "rlp", feature = "k256"))]
+impl TryFrom<CRequestRLP> for ConfidentialComputeRequest {
+    type Error = SuaveTypesError;
 
-impl CRequestHashParams {
-    fn fields_len(&self) -> usize {
-        let mut len = 0;
-        len += self.kettle_address.length();
-        len += self.confidential_inputs_hash.length();
-        len += self.nonce.length();
-        len += self.gas_price.length();
-        len += self.to.length();
-        len += self.value.length();
-        len += self.input.0.length();
-        len
+    fn try_from(rlp: CRequestRLP) -> Result<Self> {
+        let cc_record = ConfidentialComputeRecord::try_from(rlp.request)?;
+        let expected = cc_record.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH);
+        let found = rlp.confidential_inputs.hash();
+        if expected != found {
+            return Err(SuaveTypesError::ConfidentialInputsHashMismatch { expected, found });
+        }
+        Ok(ConfidentialComputeRequest {
+            confidential_compute_record: cc_record,
+            confidential_inputs: rlp.confidential_inputs,
+        })
     }
 }
 
-impl TryFrom<&ConfidentialComputeRequest> for CRequestHashParams {
-    type Error = eyre::Error;
+/// Record fields the sender signs, in the order of the 0x42-prefixed signing RLP.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "rlp", derive(RlpEncodable))]
+pub(crate) struct CRequestHashParams<'a> {
+    pub(crate) kettle_address: Address,
+    pub(crate) confidential_inputs_hash: B256,
+    pub(crate) nonce: u64,
+    pub(crate) gas_price: u128,
+    pub(crate) gas: u64,
+    pub(crate) to: Address,
+    pub(crate) value: U256,
+    pub(crate) input: &'a Bytes,
+}
 
-    fn try_from(ccr: &ConfidentialComputeRequest) -> Result<Self> {
-        let cinputs_hash = ccr.confidential_compute_record
-            .confidential_inputs_hash
-            .unwrap_or(EMPTY_BYTES_HASH);
+impl<'a> TryFrom<&'a ConfidentialComputeRecord> for CRequestHashParams<'a> {
+    type Error = SuaveTypesError;
+
+    fn try_from(crecord: &'a ConfidentialComputeRecord) -> Result<Self> {
+        let nonce = crecord.nonce.ok_or(SuaveTypesError::MissingField("nonce"))?;
+        let gas_price = crecord.gas_price.ok_or(SuaveTypesError::MissingField("gas_price"))?;
+        let gas = crecord.gas.ok_or(SuaveTypesError::MissingField("gas"))?;
+        let kettle_address = crecord.kettle_address.ok_or(SuaveTypesError::MissingField("kettle_address"))?;
         Ok(Self {
-            kettle_address: ccr.confidential_compute_record.kettle_address.ok_or_else(|| eyre!("Missing kettle address field"))?,
-            confidential_inputs_hash: cinputs_hash,
-            nonce: ccr.confidential_compute_record.nonce.ok_or_else(|| eyre!("Missing nonce field"))?,
-            gas_price: ccr.confidential_compute_record.gas_price.ok_or_else(|| eyre!("Missing gas price field"))?,
-            gas: ccr.confidential_compute_record.gas.ok_or_else(|| eyre!("Missing gas field"))?,
-            to: ccr.confidential_compute_record.to,
-            value: ccr.confidential_compute_record.value,
-            input: ccr.confidential_compute_record.input.clone(),
+            kettle_address,
+            confidential_inputs_hash: crecord.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH),
+            nonce,
+            gas_price,
+            gas: u64::try_from(gas).map_err(|_| SuaveTypesError::InvalidField("gas"))?,
+            to: crecord.to,
+            value: crecord.value,
+            input: &crecord.input,
         })
     }
 }
 
-fn encode_with_prefix<T: Encodable>(prefix: u8, item: T) -> Bytes {
-    let mut buffer = vec![prefix];
+#[cfg(feature = "rlp")]
+pub(crate) fn encode_with_prefix<T: Encodable>(prefix: u8, item: T) -> Bytes {
+    let mut buffer = Vec::with_capacity(item.length() + 1);
+    buffer.push(prefix);
     item.encode(&mut buffer);
     Bytes::from(buffer)
 }
 
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rpc"))]
 mod tests {    
     use super::*;
     use std::str::FromStr;
     use alloy::{
-        rpc::types::eth::TransactionRequest, 
+        primitives::{self, FixedBytes},
+        rpc::types::eth::{AccessList, AccessListItem, TransactionRequest}, 
         signers::wallet::LocalWallet,
         network::{
             TransactionBuilder, 
             TxSigner
         }, 
     };
-    use eyre::Ok;
+    use eyre::{Ok, Result};
+    use proptest::prelude::*;
+    use crate::test_utils::{arb_bytes, arb_signed_record, devnet_kettle};
 
     #[test]
     fn test_ccr_rlp_encode() -> Result<()> {
         let chain_id = 0x067932;
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let to_add = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
         let input = Bytes::from_str("0x236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000780675d71ebe3d3ef05fae379063071147dd3aee0000000000000000000000000000000000000000000000000000000000000000").unwrap();
         let tx = TransactionRequest::default()
@@ -292,7 +645,7 @@ mod tests {
         cc_record.set_sig(sig);
 
         let confidential_inputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307863613135656439393030366236623130363038653236313631373361313561343766383933613661222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323838222c2272223a22307863313764616536383866396262393632376563636439626636393133626661346539643232383139353134626539323066343435653263666165343366323965222c2273223a22307835633337646235386263376161336465306535656638613432353261366632653464313462613639666338323631636333623630633962643236613634626265222c2268617368223a22307862643263653662653964333461366132393934373239346662656137643461343834646663363565643963383931396533626539366131353634363630656265227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
-        let cc_request = ConfidentialComputeRequest::new(cc_record, Some(confidential_inputs));
+        let cc_request = ConfidentialComputeRequest::new(cc_record, Some(confidential_inputs.into()));
         let rlp_encoded = cc_request.rlp_encode().unwrap();
 
         let expected_rlp_encoded = Bytes::from_str("0x43f903aaf9016422843b9aca00830f424094780675d71ebe3d3ef05fae379063071147dd3aee80b8c4236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000780675d71ebe3d3ef05fae379063071147dd3aee0000000000000000000000000000000000000000000000000000000000000000947d83e42b214b75bf1f3e57adc3415da573d97bffa089ee438ca379ac86b0478517d43a6a9e078cf51543acac0facd68aff313e2ff1808306793280a01567c31c4bebcd1061edbaf22dd73fd40ff30f9a3ba4525037f23b2dc61e3473a02dce69262794a499d525c5d58edde33e06a5847b4d321d396b743700a2fd71a8b90240000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307863613135656439393030366236623130363038653236313631373361313561343766383933613661222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323838222c2272223a22307863313764616536383866396262393632376563636439626636393133626661346539643232383139353134626539323066343435653263666165343366323965222c2273223a22307835633337646235386263376161336465306535656638613432353261366632653464313462613639666338323631636333623630633962643236613634626265222c2268617368223a22307862643263653662653964333461366132393934373239346662656137643461343834646663363565643963383931396533626539366131353634363630656265227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
@@ -304,7 +657,7 @@ mod tests {
 
     #[test]
     fn test_crequesthashparams_hash() {
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let to_add = Address::from_str("0x772092ff73c43883a547bea1e1e007ec0d33478e").unwrap();
         let input = Bytes::from_str("0x236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000772092ff73c43883a547bea1e1e007ec0d33478e0000000000000000000000000000000000000000000000000000000000000000").unwrap();
         let cinputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307838626265386333346637396433353534666631626236643932313733613237666661356237313233222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323837222c2272223a22307862396433643236643135633630376237653537353235333761336163326432363330643161653036386163353138616539393862613439313236323134383135222c2273223a22307835636534666439613565376533373138656630613731386533633462333135306538373036376533373361333439323538643962333330353930396332303565222c2268617368223a22307863633934626637386463366631373963663331376638643839353438393364393730303366333266353332623530623865333861626631333939353364643664227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
@@ -318,7 +671,7 @@ mod tests {
             gas: 0x0f4240,
             to: to_add,
             value: U256::ZERO,
-            input,
+            input: &input,
         };
         let encoded = encode_with_prefix(ConfidentialComputeRecord::TYPE, hash_params);
         let hash = primitives::keccak256(&encoded);
@@ -328,8 +681,8 @@ mod tests {
     }
 
     #[test]
-    fn test_crequest_hash() {
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+    fn test_crequest_hash() -> Result<()> {
+        let kettle_address = devnet_kettle();
         let to_add = Address::from_str("0x772092ff73c43883a547bea1e1e007ec0d33478e").unwrap();
         let input = Bytes::from_str("0x236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000772092ff73c43883a547bea1e1e007ec0d33478e0000000000000000000000000000000000000000000000000000000000000000").unwrap();
         let cinputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307838626265386333346637396433353534666631626236643932313733613237666661356237313233222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323837222c2272223a22307862396433643236643135633630376237653537353235333761336163326432363330643161653036386163353138616539393862613439313236323134383135222c2273223a22307835636534666439613565376533373138656630613731386533633462333135306538373036376533373361333439323538643962333330353930396332303565222c2268617368223a22307863633934626637386463366631373963663331376638643839353438393364393730303366333266353332623530623865333861626631333939353364643664227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
@@ -348,19 +701,26 @@ mod tests {
         };
         let crequest = ConfidentialComputeRequest {
             confidential_compute_record: crecord,
-            confidential_inputs: cinputs
+            confidential_inputs: cinputs.into()
         };
-        let hash = crequest.hash();
-
         let expected_hash = FixedBytes::from_str("0x72ffab40c5116931200ca87052360787559871297b3615a8c2ff28be738ac59f").unwrap();
-        assert_eq!(hash, expected_hash);
+        assert_eq!(crequest.record().signature_hash()?, RecordSigningHash::from(expected_hash));
+        // The request hash covers the signature too.
+        assert!(matches!(crequest.request_hash(), Err(SuaveTypesError::MissingField("signature"))));
+
+        let unsigned = UnsignedConfidentialComputeRequest::try_from(crequest)?;
+        assert_eq!(unsigned.record_signing_hash()?, RecordSigningHash::from(expected_hash));
+        assert_eq!(unsigned.signature_hash(), expected_hash);
+        assert_eq!(unsigned.payload_len_for_signature(), encode_with_prefix(ConfidentialComputeRecord::TYPE, unsigned.record().hash_params()).len());
+
+        Ok(())
     }
 
     #[tokio::test]
     async fn test_ccr_sign() -> Result<()> {
         // Create a cc request 
         let cinputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307863613135656439393030366236623130363038653236313631373361313561343766383933613661222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323838222c2272223a22307863313764616536383866396262393632376563636439626636393133626661346539643232383139353134626539323066343435653263666165343366323965222c2273223a22307835633337646235386263376161336465306535656638613432353261366632653464313462613639666338323631636333623630633962643236613634626265222c2268617368223a22307862643263653662653964333461366132393934373239346662656137643461343834646663363565643963383931396533626539366131353634363630656265227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let nonce = 0x22;
         let to_add = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
         let gas = 0x0f4240;
@@ -375,7 +735,8 @@ mod tests {
             .with_nonce(nonce)
             .with_input(input);
         let cc_record = ConfidentialComputeRecord::from_tx_request(tx, kettle_address)?;
-        let mut cc_request = ConfidentialComputeRequest::new(cc_record, Some(cinputs)); 
+        let cc_request = ConfidentialComputeRequest::new(cc_record, Some(cinputs.into())); 
+        let mut cc_request = UnsignedConfidentialComputeRequest::try_from(cc_request)?;
 
         // Sign
         let pk = "0x1111111111111111111111111111111111111111111111111111111111111111";
@@ -393,7 +754,7 @@ mod tests {
     #[test]
     fn test_encode_decode() -> Result<()> {
         let cinputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307863613135656439393030366236623130363038653236313631373361313561343766383933613661222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323838222c2272223a22307863313764616536383866396262393632376563636439626636393133626661346539643232383139353134626539323066343435653263666165343366323965222c2273223a22307835633337646235386263376161336465306535656638613432353261366632653464313462613639666338323631636333623630633962643236613634626265222c2268617368223a22307862643263653662653964333461366132393934373239346662656137643461343834646663363565643963383931396533626539366131353634363630656265227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
-        let kettle_address = Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap();
+        let kettle_address = devnet_kettle();
         let nonce = 0x22;
         let to_add = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap();
         let gas = 0x0f4240;
@@ -413,15 +774,262 @@ mod tests {
         let r = U256::from_str("0x1567c31c4bebcd1061edbaf22dd73fd40ff30f9a3ba4525037f23b2dc61e3473").unwrap();
         let s = U256::from_str("0x2dce69262794a499d525c5d58edde33e06a5847b4d321d396b743700a2fd71a8").unwrap();
         cc_record.signature = Some(Signature::from_rs_and_parity(r, s, v).unwrap());
-        let cc_request = ConfidentialComputeRequest::new(cc_record, Some(cinputs));
+        let mut cc_request = ConfidentialComputeRequest::new(cc_record, Some(cinputs.into()));
+        let signed = SignedConfidentialComputeRequest::try_from(cc_request.clone())?;
 
         let mut encoded = Vec::new();
-        cc_request.encode_2718(&mut encoded);
-        let decoded = ConfidentialComputeRequest::decode_2718(&mut encoded.as_slice())?;
+        signed.encode_2718(&mut encoded);
+        assert_eq!(Bytes::from(encoded.clone()), cc_request.rlp_encode()?);
+        assert_eq!(encoded.len(), signed.encode_2718_len());
+        let decoded = SignedConfidentialComputeRequest::decode_2718(&mut encoded.as_slice())?;
+        assert_eq!(decoded, signed);
 
-        assert_eq!(cc_request, decoded);
+        let signer = Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a")?;
+        assert_eq!(decoded.recover_signer()?, signer);
+
+        cc_request.confidential_compute_record.from = Some(signer);
+        assert_eq!(decoded.into_request(), cc_request);
 
         Ok(())
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_rlp_encode_missing_fields() {
+        let cc_request = ConfidentialComputeRequest::default();
+        let err = cc_request.rlp_encode().unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("signature")));
+    }
+
+    #[test]
+    fn test_decode_errors() -> Result<()> {
+        let kettle_address = devnet_kettle();
+        let r = U256::from_str("0x1567c31c4bebcd1061edbaf22dd73fd40ff30f9a3ba4525037f23b2dc61e3473").unwrap();
+        let s = U256::from_str("0x2dce69262794a499d525c5d58edde33e06a5847b4d321d396b743700a2fd71a8").unwrap();
+        let cc_record = ConfidentialComputeRecord {
+            kettle_address: Some(kettle_address),
+            nonce: Some(0x22),
+            gas_price: Some(0x3b9aca00),
+            gas: Some(0x0f4240),
+            chain_id: Some(0x067932),
+            signature: Some(Signature::from_rs_and_parity(r, s, 0)?),
+            ..ConfidentialComputeRecord::default()
+        };
+        let mut cc_request = ConfidentialComputeRequest::new(cc_record, Some(ConfidentialBytes::from(&[1, 2, 3])));
+        cc_request.confidential_inputs = ConfidentialBytes::from(&[4, 5, 6]);
+
+        let encoded = cc_request.rlp_encode()?;
+        let err = SignedConfidentialComputeRequest::decode_2718(&mut encoded.as_ref()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("confidential inputs hash mismatch"));
+
+        let mut encoded = encoded.to_vec();
+        encoded[0] = ConfidentialComputeRecord::TYPE;
+        let err = SignedConfidentialComputeRequest::decode_2718(&mut encoded.as_slice()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("unexpected tx type"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_required_fields() {
+        let cc_request = ConfidentialComputeRequest::default();
+        let err = UnsignedConfidentialComputeRequest::try_from(cc_request.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("nonce")));
+
+        let mut cc_request = ConfidentialComputeRequest::new(ConfidentialComputeRecord {
+            nonce: Some(1),
+            gas: Some(u64::MAX as u128 + 1),
+            gas_price: Some(1),
+            chain_id: Some(0x067932),
+            ..ConfidentialComputeRecord::default()
+        }, None);
+        let err = UnsignedConfidentialComputeRequest::try_from(cc_request.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("kettle_address")));
+
+        cc_request.set_kettle_address(Address::ZERO);
+        let err = UnsignedConfidentialComputeRequest::try_from(cc_request.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("gas")));
+
+        cc_request.confidential_compute_record.gas = Some(21000);
+        cc_request.confidential_inputs = ConfidentialBytes::from(&[1]);
+        let err = UnsignedConfidentialComputeRequest::try_from(cc_request.clone()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::ConfidentialInputsHashMismatch { .. }));
+
+        cc_request.set_confidential_inputs(Bytes::from_static(&[1]));
+        let unsigned = UnsignedConfidentialComputeRequest::try_from(cc_request.clone()).unwrap();
+        assert_eq!(unsigned.gas_limit(), 21000);
+        assert_eq!(ConfidentialComputeRequest::from(unsigned), cc_request);
+
+        let err = SignedConfidentialComputeRequest::try_from(cc_request).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("signature")));
+    }
+
+    #[test]
+    fn test_unsigned_spec() -> Result<()> {
+        let cc_record = ConfidentialComputeRecord {
+            kettle_address: Some(Address::repeat_byte(0x11)),
+            nonce: Some(0x22),
+            gas_price: Some(0x3b9aca00),
+            gas: Some(0x0f4240),
+            chain_id: Some(SuaveSpec::TOLIMAN_CHAIN_ID),
+            ..ConfidentialComputeRecord::default()
+        };
+        let cc_request = ConfidentialComputeRequest::new(cc_record, None);
+        let mut unsigned = UnsignedConfidentialComputeRequest::try_from(cc_request.clone())?;
+        assert_eq!(unsigned.spec(), SuaveSpec::TOLIMAN);
+        unsigned.set_eip712(true)?;
+        assert!(unsigned.is_eip712());
+
+        // Switching to a network without EIP-712 records keeps the flag, which then fails to sign.
+        unsigned.set_chain_id(SuaveSpec::RIGIL_CHAIN_ID);
+        assert!(unsigned.is_eip712());
+        assert!(matches!(unsigned.record_signing_hash(), Err(SuaveTypesError::InvalidField("is_eip712"))));
+        assert!(matches!(unsigned.set_eip712(true), Err(SuaveTypesError::InvalidField("is_eip712"))));
+        unsigned.set_eip712(false)?;
+        let rlp_hash = primitives::keccak256(encode_with_prefix(ConfidentialComputeRecord::TYPE, unsigned.record().hash_params()));
+        assert_eq!(unsigned.record_signing_hash()?.into_b256(), rlp_hash);
+
+        let mut rigil_request = cc_request;
+        rigil_request.confidential_compute_record.chain_id = Some(SuaveSpec::RIGIL_CHAIN_ID);
+        rigil_request.confidential_compute_record.is_eip712 = true;
+        let err = UnsignedConfidentialComputeRequest::try_from(rigil_request).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tx_request_roundtrip() -> Result<()> {
+        alloy::sol_types::sol! {
+            function transfer(address to, uint256 amount);
+        }
+        let from = Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a")?;
+        let to = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?;
+        let call = transferCall { to, amount: U256::from(7) };
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(to.into())
+            .with_nonce(0x22)
+            .with_gas_limit(0x0f4240)
+            .with_gas_price(0x3b9aca00)
+            .with_chain_id(0x067932)
+            .with_value(U256::from(1))
+            .with_input(alloy::sol_types::SolCall::abi_encode(&call).into());
+
+        let cc_request = ConfidentialComputeRequest::try_from(tx.clone())?;
+        let record = &cc_request.confidential_compute_record;
+        assert_eq!(record.from, Some(from));
+        assert_eq!(record.to, to);
+        assert_eq!(record.input, tx.input.input().cloned().unwrap());
+        assert_eq!(record.kettle_address, None);
+        assert_eq!(TransactionRequest::try_from(cc_request.clone())?, tx);
+
+        let cc_record = ConfidentialComputeRecord::from_tx_request(tx, Address::repeat_byte(1))?;
+        assert_eq!(cc_record, ConfidentialComputeRecord { kettle_address: Some(Address::repeat_byte(1)), confidential_inputs_hash: None, ..record.clone() });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tx_request_unsupported_fields() -> Result<()> {
+        let to = Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?;
+        let tx = TransactionRequest::default().with_to(to.into());
+
+        let err = ConfidentialComputeRequest::try_from(TransactionRequest { max_fee_per_gas: Some(1), ..tx.clone() }).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["max_fee_per_gas"]));
+        let err = ConfidentialComputeRecord::from_tx_request(TransactionRequest { max_priority_fee_per_gas: Some(1), ..tx.clone() }, to).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["max_priority_fee_per_gas"]));
+        let access_list = Some(AccessList(vec![AccessListItem::default()]));
+        let err = ConfidentialComputeRequest::try_from(TransactionRequest { access_list: access_list.clone(), ..tx.clone() }).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["access_list"]));
+        let err = ConfidentialComputeRecord::from_tx_request(TransactionRequest { max_fee_per_gas: Some(1), access_list, ..tx.clone() }, to).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["max_fee_per_gas", "access_list"]));
+        let err = ConfidentialComputeRequest::try_from(tx.clone().transaction_type(2)).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnexpectedTxType(2)));
+        let err = ConfidentialComputeRequest::try_from(TransactionRequest::default()).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("to")));
+
+        let cc_request = ConfidentialComputeRequest::try_from(tx)?;
+        let err = TransactionRequest::try_from(cc_request.clone().with_kettle_address(to)).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["kettle_address"]));
+        let err = TransactionRequest::try_from(cc_request.with_kettle_address(to).with_confidential_inputs(b"confidential")).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::UnsupportedFields(fields) if fields == ["kettle_address", "confidential_inputs", "confidential_inputs_hash"]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_confidential_inputs() -> Result<()> {
+        alloy::sol_types::sol! {
+            #[derive(Debug, PartialEq)]
+            struct Bid {
+                uint64 decryptionCondition;
+                address[] allowedPeekers;
+            }
+        }
+        let bid = Bid { decryptionCondition: 2, allowedPeekers: vec![Address::repeat_byte(1)] };
+        let cc_request = ConfidentialComputeRequest::default().with_sol_confidential_inputs(&bid);
+        let encoded = alloy::sol_types::SolValue::abi_encode(&bid);
+        assert_eq!(cc_request.confidential_inputs().expose(), encoded);
+        assert_eq!(cc_request.confidential_compute_record.confidential_inputs_hash, Some(primitives::keccak256(&encoded)));
+        assert_eq!(cc_request.decode_confidential_inputs::<Bid>()?, bid);
+
+        // Bundles are JSON wrapped in ABI-encoded `bytes`.
+        let cinputs = Bytes::from_str("0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000001ea7b22747873223a5b7b2274797065223a22307830222c226e6f6e6365223a22307830222c22746f223a22307838626265386333346637396433353534666631626236643932313733613237666661356237313233222c22676173223a22307835323038222c226761735072696365223a22307864222c226d61785072696f72697479466565506572476173223a6e756c6c2c226d6178466565506572476173223a6e756c6c2c2276616c7565223a223078336538222c22696e707574223a223078222c2276223a2230786366323837222c2272223a22307862396433643236643135633630376237653537353235333761336163326432363330643161653036386163353138616539393862613439313236323134383135222c2273223a22307835636534666439613565376533373138656630613731386533633462333135306538373036376533373361333439323538643962333330353930396332303565222c2268617368223a22307863633934626637386463366631373963663331376638643839353438393364393730303366333266353332623530623865333861626631333939353364643664227d5d2c2270657263656e74223a31302c224d617463684964223a5b302c302c302c302c302c302c302c302c302c302c302c302c302c302c302c305d7d00000000000000000000000000000000000000000000").unwrap();
+        let cc_request = ConfidentialComputeRequest::default().with_confidential_inputs(cinputs.clone());
+        let bundle: serde_json::Value = cc_request.decode_json_confidential_inputs()?;
+        assert_eq!(bundle["percent"], 10);
+        let json = cc_request.decode_confidential_inputs::<Bytes>()?;
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&json)?, bundle);
+        let rewrapped = ConfidentialComputeRequest::default().with_sol_confidential_inputs(&json);
+        assert_eq!(rewrapped, cc_request);
+        let rebuilt = ConfidentialComputeRequest::default().with_json_confidential_inputs(&bundle)?;
+        assert_eq!(rebuilt.decode_json_confidential_inputs::<serde_json::Value>()?, bundle);
+
+        let err = cc_request.decode_confidential_inputs::<Bid>().unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MalformedConfidentialInputs(_)));
+        let err = rebuilt.with_sol_confidential_inputs(&bid).decode_json_confidential_inputs::<serde_json::Value>().unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MalformedConfidentialInputs(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_redacts_confidential_inputs() -> Result<()> {
+        let cinputs = b"private order flow";
+        let mut cc_request = ConfidentialComputeRequest::default()
+            .with_kettle_address(Address::repeat_byte(1))
+            .with_confidential_inputs(cinputs);
+        cc_request.confidential_compute_record.nonce = Some(0);
+        cc_request.confidential_compute_record.gas = Some(0);
+        cc_request.confidential_compute_record.gas_price = Some(0);
+        cc_request.confidential_compute_record.chain_id = Some(1);
+        let unsigned = UnsignedConfidentialComputeRequest::try_from(cc_request.clone())?;
+
+        let hex = alloy::primitives::hex::encode(cinputs);
+        for debug in [format!("{cc_request:?}"), format!("{cc_request:#?}"), format!("{unsigned:?}")] {
+            assert!(debug.contains("<redacted>"));
+            assert!(!debug.contains(&hex));
+        }
+        assert_eq!(unsigned.confidential_inputs().expose(), cinputs);
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_encoded_lengths(record in arb_signed_record(), confidential_inputs in arb_bytes()) {
+            let cc_request = ConfidentialComputeRequest::new(record, Some(confidential_inputs.into()));
+            let signed = SignedConfidentialComputeRequest::try_from(cc_request).unwrap();
+            let mut encoded = Vec::new();
+            signed.encode_2718(&mut encoded);
+            prop_assert_eq!(signed.encode_2718_len(), encoded.len());
+            prop_assert_eq!(signed.rlp_encode().len(), encoded.len());
+
+            let unsigned = signed.strip_signature();
+            let mut encoded = Vec::new();
+            unsigned.encode_for_signing(&mut encoded);
+            prop_assert_eq!(unsigned.payload_len_for_signature(), encoded.len());
+        }
+    }
+
+}
@@ -1,12 +1,14 @@
-use alloy::{
-    rpc::types::eth::Transaction,
-    primitives::Bytes, 
-};
-use serde::{Deserialize, Serialize};
+use alloy_primitives::Bytes;
+use alloy_rpc_types::eth::Transaction;
+use alloy_sol_types::{SolCall, SolInterface};
+use serde::{Deserialize, Deserializer, Serialize};
 use super::ConfidentialComputeRecord;
+use super::suavetx::SuaveTransaction;
+use super::error::SuaveTypesError;
+use super::hash::{RequestHash, SuaveTransactionHash};
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfidentialCallResponse {
     #[serde(flatten)]
@@ -15,41 +17,141 @@ pub struct ConfidentialCallResponse {
     pub request_record: ConfidentialComputeRecord,
 }
 
+impl ConfidentialCallResponse {
+
+    /// Hash of the 0x50 transaction, as reported by the node.
+    pub fn hash(&self) -> SuaveTransactionHash {
+        self.transaction.hash.into()
+    }
+
+    /// Transaction hash of the confidential compute request that produced this response.
+    pub fn request_hash(&self) -> Result<RequestHash, SuaveTypesError> {
+        self.request_record.request_hash()
+    }
+
+    /// Selector of the callback the result is calldata for, if the result is long enough to have one.
+    pub fn callback_selector(&self) -> Option<[u8; 4]> {
+        self.confidential_compute_result.get(..4)?.try_into().ok()
+    }
+
+    /// Decodes the result as calldata for the callback `C`, checking the selector.
+    pub fn decode_callback<C: SolCall>(&self) -> Result<C, SuaveTypesError> {
+        C::abi_decode(&self.confidential_compute_result, true).map_err(invalid_result)
+    }
+
+    /// Decodes the result as calldata for a function of the contract interface `I`; the variant
+    /// is the callback it targets.
+    pub fn decode_callback_interface<I: SolInterface>(&self) -> Result<I, SuaveTypesError> {
+        I::abi_decode(&self.confidential_compute_result, true).map_err(invalid_result)
+    }
+
+    /// Decodes the result as the return data of `C`, for offchain functions called without a callback.
+    pub fn decode_result<C: SolCall>(&self) -> Result<C::Return, SuaveTypesError> {
+        C::abi_decode_returns(&self.confidential_compute_result, true).map_err(invalid_result)
+    }
+
+}
+
 impl TryFrom<Transaction> for ConfidentialCallResponse {
-    type Error = eyre::Error;
-
-    fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
-        let confidential_compute_result = serde_json::from_value(
-            tx.other.get("confidentialComputeResult")
-                .ok_or(eyre::eyre!("Missing confidentialComputeResult"))?.clone()
-            )?;
-        let confidential_compute_record = serde_json::from_value(
-            tx.other.get("requestRecord")
-                .ok_or(eyre::eyre!("Missing requestRecord"))?.clone()
-            )?;
+    type Error = SuaveTypesError;
+
+    /// Moves the SUAVE fields out of `tx.other`. Deserializing the response directly
+    /// avoids parsing them twice.
+    fn try_from(mut tx: Transaction) -> Result<Self, Self::Error> {
+        let confidential_compute_result = take_other_field(&mut tx, "confidentialComputeResult")?;
+        let request_record = take_other_field(&mut tx, "requestRecord")?;
+        SuaveRpcTransaction { transaction: tx, confidential_compute_result, request_record }.try_into()
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfidentialCallResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SuaveRpcTransaction::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Transaction as returned by a SUAVE node, with the confidential call fields parsed
+/// alongside the transaction instead of being left in `other`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SuaveRpcTransaction {
+    #[serde(flatten)]
+    pub(crate) transaction: Transaction,
+    pub(crate) confidential_compute_result: Option<Bytes>,
+    pub(crate) request_record: Option<ConfidentialComputeRecord>,
+}
+
+impl SuaveRpcTransaction {
+
+    pub(crate) fn is_suave_tx(&self) -> bool {
+        self.transaction.transaction_type == Some(SuaveTransaction::TYPE) || self.request_record.is_some()
+    }
+
+    /// Puts the confidential call fields back into `other`, for transactions that aren't SUAVE transactions.
+    pub(crate) fn into_transaction(self) -> Transaction {
+        let mut tx = self.transaction;
+        if let Some(result) = self.confidential_compute_result {
+            tx.other.insert("confidentialComputeResult".into(), serde_json::to_value(result).expect("Bytes serialize"));
+        }
+        tx
+    }
+
+}
+
+impl TryFrom<SuaveRpcTransaction> for ConfidentialCallResponse {
+    type Error = SuaveTypesError;
+
+    fn try_from(tx: SuaveRpcTransaction) -> Result<Self, Self::Error> {
+        let confidential_compute_result = tx.confidential_compute_result
+            .ok_or_else(|| SuaveTypesError::MalformedResponse("missing confidentialComputeResult".into()))?;
+        let mut request_record = tx.request_record
+            .ok_or_else(|| SuaveTypesError::MalformedResponse("missing requestRecord".into()))?;
+        // Keeps a `from` the node wrote, and doesn't fail the response over a sender it can't recover.
+        if request_record.from.is_none() {
+            request_record.from = request_record.recover_signer().ok();
+        }
 
         Ok(Self {
-            transaction: tx,
+            transaction: tx.transaction,
             confidential_compute_result,
-            request_record: confidential_compute_record,
+            request_record,
         })
     }
 }
 
+fn invalid_result(err: alloy_sol_types::Error) -> SuaveTypesError {
+    SuaveTypesError::MalformedResponse(format!("invalid confidentialComputeResult: {err}"))
+}
+
+fn take_other_field<T: serde::de::DeserializeOwned>(
+    tx: &mut Transaction, 
+    key: &str
+) -> Result<Option<T>, SuaveTypesError> {
+    tx.other.remove(key)
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| SuaveTypesError::MalformedResponse(format!("invalid {key}: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::primitives::{FixedBytes, U256, Address};
     use std::str::FromStr;
     use super::*;
+    use crate::test_utils::RESPONSE;
     use super::super::crecord::signature_to_vrs;
 
     #[test]
     fn test_parse_response() {
-        let response_str = r#"{"blockHash":null,"blockNumber":null,"chainId":"0x1008c45","confidentialComputeResult":"0x0000000000000000000000000000000000000000000000000000000001ccb310","from":"0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","input":"0x0000000000000000000000000000000000000000000000000000000001ccb310","nonce":"0x45","r":"0x85242d1876ce1d6a655fd485346628f3df18a051be0f8efa4bfa40b9e85a3dfe","requestRecord":{"chainId":"0x1008c45","confidentialInputsHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9","input":"0x50723553000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000074554485553445400","kettleAddress":"0x03493869959c866713c33669ca118e774a30a0e5","maxFeePerGas":null,"maxPriorityFeePerGas":null,"nonce":"0x45","r":"0xc1c5071f78c6f6b6380ebc4957dd4f6c74bdf5be742ad0d62d2d75f510e33660","s":"0x5de5c97f9c5ee5c5dad3bb0d591e581f48cd947e998d32500bb73de24dd7a6f9","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","type":"0x42","v":"0x0","value":"0x0"},"s":"0x4f0880f42d42b1de17f97c33749d60a46bd1f493c6547f08ac2bed0c6d111861","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionIndex":null,"type":"0x50","v":"0x1","value":"0x0"}"#;
+        let response_str = RESPONSE;
         let response_tx: Transaction = serde_json::from_str(response_str).unwrap();
         let response_cc: ConfidentialCallResponse = response_tx.clone().try_into().unwrap();
 
-        assert_eq!(response_cc.transaction, response_tx);
+        assert_eq!(response_cc.transaction.hash, response_tx.hash);
+        assert!(response_cc.transaction.other.is_empty());
+        assert_eq!(serde_json::from_str::<ConfidentialCallResponse>(response_str).unwrap(), response_cc);
         assert_eq!(response_cc.confidential_compute_result, Bytes::from_str("0x0000000000000000000000000000000000000000000000000000000001ccb310").unwrap());
         assert_eq!(response_cc.request_record.chain_id, Some(0x1008c45));
         assert_eq!(response_cc.request_record.gas, Some(0xf4240));
@@ -59,6 +161,8 @@ mod tests {
         assert_eq!(response_cc.request_record.kettle_address, Address::from_str("0x03493869959c866713c33669ca118e774a30a0e5").ok());
         assert_eq!(response_cc.request_record.to, Address::from_str("0xc803334c79650708daf3a3462ac4b48296b1352a").unwrap());
         assert_eq!(response_cc.request_record.confidential_inputs_hash, Some(FixedBytes::from_str("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap()));
+        assert_eq!(response_cc.request_record.from, Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a").ok());
+        assert_eq!(response_cc.request_record.recover_signer().unwrap(), response_cc.transaction.from);
         
         let sig = response_cc.request_record.signature.expect("No signature");
         let (v, r, s) = signature_to_vrs(sig);
@@ -66,4 +170,66 @@ mod tests {
         assert_eq!(r, U256::from_str("0xc1c5071f78c6f6b6380ebc4957dd4f6c74bdf5be742ad0d62d2d75f510e33660").unwrap());
         assert_eq!(s, U256::from_str("0x5de5c97f9c5ee5c5dad3bb0d591e581f48cd947e998d32500bb73de24dd7a6f9").unwrap());    
     }
+
+    #[test]
+    fn test_decode_result() {
+        alloy::sol_types::sol! {
+            #[derive(Debug, PartialEq)]
+            interface BinanceOracle {
+                function queryLatestPrice(string ticker) returns (uint256 price);
+                function updatePrice(string ticker, uint256 price);
+                function registerKettle(address kettle);
+            }
+        }
+        use BinanceOracle::{queryLatestPriceCall, updatePriceCall, BinanceOracleCalls};
+
+        let mut response: ConfidentialCallResponse = serde_json::from_str(RESPONSE).unwrap();
+        // The kettle accepted the ticker without its word padding.
+        let query = queryLatestPriceCall::abi_decode(&response.request_record.input, false).unwrap();
+        assert_eq!(query.ticker, "ETHUSDT");
+        assert_eq!(response.decode_result::<queryLatestPriceCall>().unwrap().price, U256::from(0x01ccb310));
+        assert!(matches!(response.decode_callback::<updatePriceCall>(), Err(SuaveTypesError::MalformedResponse(_))));
+
+        let callback = updatePriceCall { ticker: query.ticker, price: U256::from(0x01ccb310) };
+        response.confidential_compute_result = callback.abi_encode().into();
+        assert_eq!(response.callback_selector(), Some(updatePriceCall::SELECTOR));
+        assert_eq!(response.decode_callback::<updatePriceCall>().unwrap(), callback);
+        let call = response.decode_callback_interface::<BinanceOracleCalls>().unwrap();
+        assert_eq!(call, BinanceOracleCalls::updatePrice(callback));
+        assert_eq!(call.selector(), updatePriceCall::SELECTOR);
+
+        response.confidential_compute_result = Bytes::new();
+        assert_eq!(response.callback_selector(), None);
+        assert!(response.decode_callback_interface::<BinanceOracleCalls>().is_err());
+    }
+
+    #[test]
+    fn test_parse_response_missing_fields() {
+        let response_str = r#"{"blockHash":null,"blockNumber":null,"chainId":"0x1008c45","from":"0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","input":"0x","nonce":"0x45","r":"0x85242d1876ce1d6a655fd485346628f3df18a051be0f8efa4bfa40b9e85a3dfe","s":"0x4f0880f42d42b1de17f97c33749d60a46bd1f493c6547f08ac2bed0c6d111861","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionIndex":null,"type":"0x0","v":"0x1","value":"0x0"}"#;
+        let response_tx: Transaction = serde_json::from_str(response_str).unwrap();
+        let err = ConfidentialCallResponse::try_from(response_tx).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MalformedResponse(msg) if msg == "missing confidentialComputeResult"));
+    }
+
+    #[test]
+    fn test_parse_response_invalid_record() {
+        let response_str = r#"{"blockHash":null,"blockNumber":null,"chainId":"0x1008c45","confidentialComputeResult":"0x","from":"0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","input":"0x","nonce":"0x45","r":"0x85242d1876ce1d6a655fd485346628f3df18a051be0f8efa4bfa40b9e85a3dfe","requestRecord":{"nonce":"not a number"},"s":"0x4f0880f42d42b1de17f97c33749d60a46bd1f493c6547f08ac2bed0c6d111861","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionIndex":null,"type":"0x50","v":"0x1","value":"0x0"}"#;
+        let response_tx: Transaction = serde_json::from_str(response_str).unwrap();
+        let err = ConfidentialCallResponse::try_from(response_tx).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MalformedResponse(msg) if msg.starts_with("invalid requestRecord")));
+        assert!(serde_json::from_str::<ConfidentialCallResponse>(response_str).is_err());
+    }
+
+    #[test]
+    fn test_parse_response_unrecoverable_sender() {
+        let mut response: serde_json::Value = serde_json::from_str(RESPONSE).unwrap();
+        // Rigil records can't be signed as EIP-712 typed data, so there is no hash to recover from.
+        response["requestRecord"]["isEIP712"] = serde_json::json!(true);
+        let response_cc: ConfidentialCallResponse = serde_json::from_value(response.clone()).unwrap();
+        assert_eq!(response_cc.request_record.from, None);
+
+        response["requestRecord"]["from"] = serde_json::json!(Address::repeat_byte(0x11));
+        let response_cc: ConfidentialCallResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(response_cc.request_record.from, Some(Address::repeat_byte(0x11)));
+    }
 }
\ No newline at end of file
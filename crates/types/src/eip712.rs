@@ -0,0 +1,222 @@
+use alloc::borrow::Cow;
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{sol, Eip712Domain, SolStruct};
+#[cfg(feature = "serde")]
+use serde_json::json;
+use super::crecord::ConfidentialComputeRecord;
+use super::crequest::CRequestHashParams;
+use super::error::{Result, SuaveTypesError};
+
+
+/// EIP-712 domain name suave-geth uses for confidential compute records.
+pub const EIP712_DOMAIN_NAME: &str = "ConfidentialRecord";
+
+sol! {
+    /// EIP-712 typed-data representation of a confidential compute record.
+    #[derive(Debug, PartialEq)]
+    struct ConfidentialRecord {
+        uint64 nonce;
+        uint256 gasPrice;
+        uint64 gas;
+        address to;
+        uint256 value;
+        bytes data;
+        address kettleAddress;
+        bytes32 confidentialInputsHash;
+    }
+}
+
+impl ConfidentialComputeRecord {
+
+    /// EIP-712 domain of the record, with the kettle as the verifying contract.
+    pub fn eip712_domain(&self) -> Result<Eip712Domain> {
+        let kettle_address = self.kettle_address
+            .ok_or(SuaveTypesError::MissingField("kettle_address"))?;
+        Ok(eip712_domain(kettle_address))
+    }
+
+    /// EIP-712 struct of the record, fails on networks without EIP-712 records.
+    pub fn eip712_struct(&self) -> Result<ConfidentialRecord> {
+        self.spec().eip712_record(&CRequestHashParams::try_from(self)?)
+    }
+
+    pub fn eip712_struct_hash(&self) -> Result<B256> {
+        Ok(self.eip712_struct()?.eip712_hash_struct())
+    }
+
+    /// Hash signed by the sender when `is_eip712` is set.
+    pub fn eip712_signing_hash(&self) -> Result<B256> {
+        Ok(self.eip712_struct()?.eip712_signing_hash(&self.eip712_domain()?))
+    }
+
+    /// Typed data for `eth_signTypedData_v4`.
+    #[cfg(feature = "serde")]
+    pub fn eip712_typed_data(&self) -> Result<serde_json::Value> {
+        let record = self.eip712_struct()?;
+        Ok(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "ConfidentialRecord": [
+                    { "name": "nonce", "type": "uint64" },
+                    { "name": "gasPrice", "type": "uint256" },
+                    { "name": "gas", "type": "uint64" },
+                    { "name": "to", "type": "address" },
+                    { "name": "value", "type": "uint256" },
+                    { "name": "data", "type": "bytes" },
+                    { "name": "kettleAddress", "type": "address" },
+                    { "name": "confidentialInputsHash", "type": "bytes32" },
+                ],
+            },
+            "primaryType": "ConfidentialRecord",
+            "domain": {
+                "name": EIP712_DOMAIN_NAME,
+                "verifyingContract": record.kettleAddress,
+            },
+            "message": {
+                "nonce": format!("{:#x}", record.nonce),
+                "gasPrice": format!("{:#x}", record.gasPrice),
+                "gas": format!("{:#x}", record.gas),
+                "to": record.to,
+                "value": format!("{:#x}", record.value),
+                "data": record.data,
+                "kettleAddress": record.kettleAddress,
+                "confidentialInputsHash": record.confidentialInputsHash,
+            },
+        }))
+    }
+
+}
+
+impl From<&CRequestHashParams<'_>> for ConfidentialRecord {
+    fn from(params: &CRequestHashParams<'_>) -> Self {
+        ConfidentialRecord {
+            nonce: params.nonce,
+            gasPrice: U256::from(params.gas_price),
+            gas: params.gas,
+            to: params.to,
+            value: params.value,
+            data: params.input.clone(),
+            kettleAddress: params.kettle_address,
+            confidentialInputsHash: params.confidential_inputs_hash,
+        }
+    }
+}
+
+pub(crate) fn eip712_domain(kettle_address: Address) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(Cow::Borrowed(EIP712_DOMAIN_NAME)),
+        None,
+        None,
+        Some(kettle_address),
+        None,
+    )
+}
+
+/// Length of the EIP-712 signing payload: the `0x19 0x01` prefix, domain separator and struct hash.
+#[cfg(feature = "consensus")]
+pub(crate) const EIP712_SIGNING_PAYLOAD_LEN: usize = 1 + 1 + 32 + 32;
+
+/// Payload whose keccak is the EIP-712 signing hash: `0x1901 || domainSeparator || structHash`.
+#[cfg(feature = "consensus")]
+pub(crate) fn eip712_signing_payload(
+    domain: &Eip712Domain,
+    record: &ConfidentialRecord,
+) -> [u8; EIP712_SIGNING_PAYLOAD_LEN] {
+    let mut payload = [0u8; EIP712_SIGNING_PAYLOAD_LEN];
+    payload[0] = 0x19;
+    payload[1] = 0x01;
+    payload[2..34].copy_from_slice(domain.separator().as_slice());
+    payload[34..].copy_from_slice(record.eip712_hash_struct().as_slice());
+    payload
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use std::str::FromStr;
+    use alloy::{
+        primitives::keccak256,
+        consensus::SignableTransaction,
+        signers::wallet::LocalWallet,
+        network::TxSigner,
+    };
+    use eyre::Result;
+    use super::*;
+    use super::super::{ConfidentialBytes, ConfidentialComputeRequest, UnsignedConfidentialComputeRequest};
+    use crate::test_utils::devnet_record;
+
+    fn eip712_request() -> ConfidentialComputeRequest {
+        let cc_record = ConfidentialComputeRecord {
+            confidential_inputs_hash: None,
+            is_eip712: true,
+            signature: None,
+            ..devnet_record()
+        };
+        ConfidentialComputeRequest::new(cc_record, Some(ConfidentialBytes::from(b"confidential")))
+    }
+
+    #[test]
+    fn test_eip712_hash_matches_spec_encoding() -> Result<()> {
+        let cc_request = eip712_request();
+        let record = &cc_request.confidential_compute_record;
+
+        let domain_type_hash = keccak256("EIP712Domain(string name,address verifyingContract)");
+        let mut domain = domain_type_hash.to_vec();
+        domain.extend_from_slice(keccak256(EIP712_DOMAIN_NAME).as_slice());
+        domain.extend_from_slice(record.kettle_address.unwrap().into_word().as_slice());
+        let domain_separator = keccak256(&domain);
+
+        let type_hash = keccak256("ConfidentialRecord(uint64 nonce,uint256 gasPrice,uint64 gas,address to,uint256 value,bytes data,address kettleAddress,bytes32 confidentialInputsHash)");
+        let mut data = type_hash.to_vec();
+        data.extend_from_slice(&U256::from(record.nonce.unwrap()).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(record.gas_price.unwrap()).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(record.gas.unwrap()).to_be_bytes::<32>());
+        data.extend_from_slice(record.to.into_word().as_slice());
+        data.extend_from_slice(&record.value.to_be_bytes::<32>());
+        data.extend_from_slice(keccak256(&record.input).as_slice());
+        data.extend_from_slice(record.kettle_address.unwrap().into_word().as_slice());
+        data.extend_from_slice(record.confidential_inputs_hash.unwrap().as_slice());
+        let struct_hash = keccak256(&data);
+
+        let mut payload = vec![0x19, 0x01];
+        payload.extend_from_slice(domain_separator.as_slice());
+        payload.extend_from_slice(struct_hash.as_slice());
+        let expected_hash = keccak256(&payload);
+
+        assert_eq!(record.eip712_domain()?.separator(), domain_separator);
+        assert_eq!(record.eip712_struct_hash()?, struct_hash);
+        assert_eq!(record.eip712_signing_hash()?, expected_hash);
+        let unsigned = UnsignedConfidentialComputeRequest::try_from(cc_request.clone())?;
+        assert_eq!(unsigned.signature_hash(), expected_hash);
+        assert_eq!(unsigned.payload_len_for_signature(), payload.len());
+
+        Ok(())
+    }
+
+    // The signature is this crate's own output, a regression value rather than a suave-geth one:
+    // no EIP-712 record signed by suave-geth has been captured. The typed-data hash is checked
+    // against the spec in `test_eip712_hash`, and the signer recovers from it below.
+    #[tokio::test]
+    async fn test_eip712_sign() -> Result<()> {
+        let cc_request = eip712_request();
+        let mut unsigned = UnsignedConfidentialComputeRequest::try_from(cc_request.clone())?;
+        let pk = "0x1111111111111111111111111111111111111111111111111111111111111111";
+        let wallet: LocalWallet = pk.parse()?;
+        let sig = wallet.sign_transaction(&mut unsigned).await?;
+
+        let signing_hash = cc_request.confidential_compute_record.eip712_signing_hash()?;
+        let expected_hash = B256::from_str("0x8db0c689d75284a117ef89b0628b334594565e1dcdc77dc350ce07f6b2cd8896")?;
+        assert_eq!(signing_hash, expected_hash);
+
+        assert_eq!(sig.v().recid().to_byte(), 0_u8);
+        assert_eq!(sig.r(), U256::from_str("0xb8bcdfe114748cb96cd9c9b62a87f43ac333a363a5603e10b42cd637dd2a7a2c")?);
+        assert_eq!(sig.s(), U256::from_str("0x27ff568dbf8056976d724fb766923f1ce2caa4b5a7f7f2378c0824fdbfad306e")?);
+        assert_eq!(sig.recover_address_from_prehash(&signing_hash)?, wallet.address());
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,535 @@
+use alloy_rlp::Header;
+use alloy_consensus::{Signed, TxEip1559, TxEip2930, TxEnvelope, TxLegacy, TypedTransaction};
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Encodable2718};
+use alloy_primitives::{Address, Signature};
+use super::crecord::{ConfidentialComputeRecord, SignedConfidentialComputeRecord, UnsignedConfidentialComputeRecord};
+use super::crequest::{ConfidentialComputeRequest, SignedConfidentialComputeRequest, UnsignedConfidentialComputeRequest};
+use super::suavetx::SuaveTransaction;
+use super::error::{Result, SuaveTypesError};
+
+
+/// EIP-2718 transaction types accepted by SUAVE chains.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SuaveTxType {
+    /// Legacy transaction type.
+    Legacy = 0,
+    /// EIP-2930 transaction type.
+    Eip2930 = 1,
+    /// EIP-1559 transaction type.
+    Eip1559 = 2,
+    /// Signed confidential compute record.
+    ConfidentialComputeRecord = ConfidentialComputeRecord::TYPE,
+    /// Confidential compute request, the record with its confidential inputs.
+    ConfidentialComputeRequest = ConfidentialComputeRequest::TYPE,
+    /// Kettle-signed transaction with the confidential compute result.
+    SuaveTransaction = SuaveTransaction::TYPE,
+}
+
+impl From<SuaveTxType> for u8 {
+    fn from(value: SuaveTxType) -> Self {
+        value as u8
+    }
+}
+
+impl TryFrom<u8> for SuaveTxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> core::result::Result<Self, Self::Error> {
+        Ok(match value {
+            0 => SuaveTxType::Legacy,
+            1 => SuaveTxType::Eip2930,
+            2 => SuaveTxType::Eip1559,
+            ConfidentialComputeRecord::TYPE => SuaveTxType::ConfidentialComputeRecord,
+            ConfidentialComputeRequest::TYPE => SuaveTxType::ConfidentialComputeRequest,
+            SuaveTransaction::TYPE => SuaveTxType::SuaveTransaction,
+            _ => return Err(Eip2718Error::UnexpectedType(value)),
+        })
+    }
+}
+
+impl core::fmt::Display for SuaveTxType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SuaveTxType::Legacy => write!(f, "Legacy"),
+            SuaveTxType::Eip2930 => write!(f, "EIP-2930"),
+            SuaveTxType::Eip1559 => write!(f, "EIP-1559"),
+            SuaveTxType::ConfidentialComputeRecord => write!(f, "ConfidentialComputeRecord"),
+            SuaveTxType::ConfidentialComputeRequest => write!(f, "ConfidentialComputeRequest"),
+            SuaveTxType::SuaveTransaction => write!(f, "SuaveTransaction"),
+        }
+    }
+}
+
+/// Signed transaction of any type accepted by SUAVE chains.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuaveTxEnvelope {
+    Legacy(Signed<TxLegacy>),
+    Eip2930(Signed<TxEip2930>),
+    Eip1559(Signed<TxEip1559>),
+    ConfidentialComputeRecord(SignedConfidentialComputeRecord),
+    ConfidentialComputeRequest(SignedConfidentialComputeRequest),
+    SuaveTransaction(SuaveTransaction),
+}
+
+impl SuaveTxEnvelope {
+
+    pub fn tx_type(&self) -> SuaveTxType {
+        match self {
+            SuaveTxEnvelope::Legacy(_) => SuaveTxType::Legacy,
+            SuaveTxEnvelope::Eip2930(_) => SuaveTxType::Eip2930,
+            SuaveTxEnvelope::Eip1559(_) => SuaveTxType::Eip1559,
+            SuaveTxEnvelope::ConfidentialComputeRecord(_) => SuaveTxType::ConfidentialComputeRecord,
+            SuaveTxEnvelope::ConfidentialComputeRequest(_) => SuaveTxType::ConfidentialComputeRequest,
+            SuaveTxEnvelope::SuaveTransaction(_) => SuaveTxType::SuaveTransaction,
+        }
+    }
+
+    pub fn is_confidential(&self) -> bool {
+        matches!(
+            self,
+            SuaveTxEnvelope::ConfidentialComputeRecord(_) | SuaveTxEnvelope::ConfidentialComputeRequest(_)
+        )
+    }
+
+    /// Recovers the signer of the transaction, which is the kettle for a [`SuaveTransaction`].
+    pub fn recover_signer(&self) -> Result<Address> {
+        match self {
+            SuaveTxEnvelope::Legacy(tx) => Ok(tx.recover_signer()?),
+            SuaveTxEnvelope::Eip2930(tx) => Ok(tx.recover_signer()?),
+            SuaveTxEnvelope::Eip1559(tx) => Ok(tx.recover_signer()?),
+            SuaveTxEnvelope::ConfidentialComputeRecord(tx) => tx.recover_signer(),
+            SuaveTxEnvelope::ConfidentialComputeRequest(tx) => tx.recover_signer(),
+            SuaveTxEnvelope::SuaveTransaction(tx) => tx.recover_signer(),
+        }
+    }
+
+}
+
+impl Encodable2718 for SuaveTxEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            SuaveTxEnvelope::Legacy(_) => None,
+            _ => Some(self.tx_type().into()),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        match self {
+            SuaveTxEnvelope::Legacy(tx) => {
+                let payload_length = tx.tx().fields_len() + tx.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length
+            }
+            SuaveTxEnvelope::Eip2930(tx) => {
+                let payload_length = tx.tx().fields_len() + tx.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length + 1
+            }
+            SuaveTxEnvelope::Eip1559(tx) => {
+                let payload_length = tx.tx().fields_len() + tx.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length + 1
+            }
+            SuaveTxEnvelope::ConfidentialComputeRecord(tx) => tx.encode_2718_len(),
+            SuaveTxEnvelope::ConfidentialComputeRequest(tx) => tx.encode_2718_len(),
+            SuaveTxEnvelope::SuaveTransaction(tx) => tx.encode_2718_len(),
+        }
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        match self {
+            SuaveTxEnvelope::Legacy(tx) => tx.tx().encode_with_signature_fields(tx.signature(), out),
+            SuaveTxEnvelope::Eip2930(tx) => tx.tx().encode_with_signature(tx.signature(), out, false),
+            SuaveTxEnvelope::Eip1559(tx) => tx.tx().encode_with_signature(tx.signature(), out, false),
+            SuaveTxEnvelope::ConfidentialComputeRecord(tx) => tx.encode_2718(out),
+            SuaveTxEnvelope::ConfidentialComputeRequest(tx) => tx.encode_2718(out),
+            SuaveTxEnvelope::SuaveTransaction(tx) => tx.encode_2718(out),
+        }
+    }
+}
+
+impl Decodable2718 for SuaveTxEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let tx_type = SuaveTxType::try_from(ty)
+            .map_err(|_| SuaveTypesError::UnexpectedTxType(ty))?;
+        match tx_type {
+            SuaveTxType::Legacy => Err(SuaveTypesError::UnexpectedTxType(ty).into()),
+            SuaveTxType::Eip2930 => Ok(TxEip2930::decode_signed_fields(buf)?.into()),
+            SuaveTxType::Eip1559 => Ok(TxEip1559::decode_signed_fields(buf)?.into()),
+            SuaveTxType::ConfidentialComputeRecord => {
+                Ok(SignedConfidentialComputeRecord::typed_decode(ty, buf)?.into())
+            }
+            SuaveTxType::ConfidentialComputeRequest => {
+                Ok(SignedConfidentialComputeRequest::typed_decode(ty, buf)?.into())
+            }
+            SuaveTxType::SuaveTransaction => Ok(SuaveTransaction::typed_decode(ty, buf)?.into()),
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(TxLegacy::decode_signed_fields(buf)?.into())
+    }
+}
+
+impl From<Signed<TxLegacy>> for SuaveTxEnvelope {
+    fn from(tx: Signed<TxLegacy>) -> Self {
+        SuaveTxEnvelope::Legacy(tx)
+    }
+}
+
+impl From<Signed<TxEip2930>> for SuaveTxEnvelope {
+    fn from(tx: Signed<TxEip2930>) -> Self {
+        SuaveTxEnvelope::Eip2930(tx)
+    }
+}
+
+impl From<Signed<TxEip1559>> for SuaveTxEnvelope {
+    fn from(tx: Signed<TxEip1559>) -> Self {
+        SuaveTxEnvelope::Eip1559(tx)
+    }
+}
+
+impl From<SignedConfidentialComputeRecord> for SuaveTxEnvelope {
+    fn from(tx: SignedConfidentialComputeRecord) -> Self {
+        SuaveTxEnvelope::ConfidentialComputeRecord(tx)
+    }
+}
+
+impl From<SignedConfidentialComputeRequest> for SuaveTxEnvelope {
+    fn from(tx: SignedConfidentialComputeRequest) -> Self {
+        SuaveTxEnvelope::ConfidentialComputeRequest(tx)
+    }
+}
+
+impl From<SuaveTransaction> for SuaveTxEnvelope {
+    fn from(tx: SuaveTransaction) -> Self {
+        SuaveTxEnvelope::SuaveTransaction(tx)
+    }
+}
+
+impl TryFrom<TxEnvelope> for SuaveTxEnvelope {
+    type Error = SuaveTypesError;
+
+    fn try_from(envelope: TxEnvelope) -> Result<Self> {
+        match envelope {
+            TxEnvelope::Legacy(tx) => Ok(tx.into()),
+            TxEnvelope::Eip2930(tx) => Ok(tx.into()),
+            TxEnvelope::Eip1559(tx) => Ok(tx.into()),
+            other => Err(SuaveTypesError::UnexpectedTxType(other.tx_type() as u8)),
+        }
+    }
+}
+
+/// Unsigned transaction of any type a SUAVE user can sign.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuaveTypedTransaction {
+    Legacy(TxLegacy),
+    Eip2930(TxEip2930),
+    Eip1559(TxEip1559),
+    ConfidentialComputeRecord(UnsignedConfidentialComputeRecord),
+    ConfidentialComputeRequest(UnsignedConfidentialComputeRequest),
+    /// Kept whole, as only the kettle can sign it; signing replaces the kettle signature.
+    SuaveTransaction(Box<SuaveTransaction>),
+}
+
+impl SuaveTypedTransaction {
+
+    pub fn tx_type(&self) -> SuaveTxType {
+        match self {
+            SuaveTypedTransaction::Legacy(_) => SuaveTxType::Legacy,
+            SuaveTypedTransaction::Eip2930(_) => SuaveTxType::Eip2930,
+            SuaveTypedTransaction::Eip1559(_) => SuaveTxType::Eip1559,
+            SuaveTypedTransaction::ConfidentialComputeRecord(_) => SuaveTxType::ConfidentialComputeRecord,
+            SuaveTypedTransaction::ConfidentialComputeRequest(_) => SuaveTxType::ConfidentialComputeRequest,
+            SuaveTypedTransaction::SuaveTransaction(_) => SuaveTxType::SuaveTransaction,
+        }
+    }
+
+    /// Attaches `signature` to the transaction.
+    pub fn into_signed(self, signature: Signature) -> SuaveTxEnvelope {
+        use alloy_consensus::SignableTransaction;
+        match self {
+            SuaveTypedTransaction::Legacy(tx) => tx.into_signed(signature).into(),
+            SuaveTypedTransaction::Eip2930(tx) => tx.into_signed(signature).into(),
+            SuaveTypedTransaction::Eip1559(tx) => tx.into_signed(signature).into(),
+            SuaveTypedTransaction::ConfidentialComputeRecord(tx) => {
+                SignedConfidentialComputeRecord::from(tx.into_signed(signature)).into()
+            }
+            SuaveTypedTransaction::ConfidentialComputeRequest(tx) => {
+                SignedConfidentialComputeRequest::from(tx.into_signed(signature)).into()
+            }
+            SuaveTypedTransaction::SuaveTransaction(tx) => (*tx).with_signature(signature).into(),
+        }
+    }
+
+}
+
+impl From<TxLegacy> for SuaveTypedTransaction {
+    fn from(tx: TxLegacy) -> Self {
+        SuaveTypedTransaction::Legacy(tx)
+    }
+}
+
+impl From<TxEip2930> for SuaveTypedTransaction {
+    fn from(tx: TxEip2930) -> Self {
+        SuaveTypedTransaction::Eip2930(tx)
+    }
+}
+
+impl From<TxEip1559> for SuaveTypedTransaction {
+    fn from(tx: TxEip1559) -> Self {
+        SuaveTypedTransaction::Eip1559(tx)
+    }
+}
+
+impl From<UnsignedConfidentialComputeRecord> for SuaveTypedTransaction {
+    fn from(tx: UnsignedConfidentialComputeRecord) -> Self {
+        SuaveTypedTransaction::ConfidentialComputeRecord(tx)
+    }
+}
+
+impl From<UnsignedConfidentialComputeRequest> for SuaveTypedTransaction {
+    fn from(tx: UnsignedConfidentialComputeRequest) -> Self {
+        SuaveTypedTransaction::ConfidentialComputeRequest(tx)
+    }
+}
+
+impl From<SuaveTransaction> for SuaveTypedTransaction {
+    fn from(tx: SuaveTransaction) -> Self {
+        SuaveTypedTransaction::SuaveTransaction(Box::new(tx))
+    }
+}
+
+impl TryFrom<TypedTransaction> for SuaveTypedTransaction {
+    type Error = SuaveTypesError;
+
+    fn try_from(tx: TypedTransaction) -> Result<Self> {
+        match tx {
+            TypedTransaction::Legacy(tx) => Ok(tx.into()),
+            TypedTransaction::Eip2930(tx) => Ok(tx.into()),
+            TypedTransaction::Eip1559(tx) => Ok(tx.into()),
+            other => Err(SuaveTypesError::UnexpectedTxType(other.tx_type() as u8)),
+        }
+    }
+}
+
+impl From<SuaveTxEnvelope> for SuaveTypedTransaction {
+    fn from(envelope: SuaveTxEnvelope) -> Self {
+        match envelope {
+            SuaveTxEnvelope::Legacy(tx) => tx.strip_signature().into(),
+            SuaveTxEnvelope::Eip2930(tx) => tx.strip_signature().into(),
+            SuaveTxEnvelope::Eip1559(tx) => tx.strip_signature().into(),
+            SuaveTxEnvelope::ConfidentialComputeRecord(tx) => tx.strip_signature().into(),
+            SuaveTxEnvelope::ConfidentialComputeRequest(tx) => tx.strip_signature().into(),
+            SuaveTxEnvelope::SuaveTransaction(tx) => SuaveTypedTransaction::SuaveTransaction(Box::new(tx)),
+        }
+    }
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use std::str::FromStr;
+    use alloy::{
+        consensus::SignableTransaction,
+        primitives::{Bytes, TxKind, U256},
+        signers::Signer,
+        signers::wallet::LocalWallet,
+    };
+    use eyre::Result;
+    use super::*;
+    use crate::ConfidentialBytes;
+    use proptest::prelude::*;
+    use crate::test_utils::{arb_address, arb_bytes, arb_signature, arb_u128, arb_u256, arb_u64, devnet_kettle};
+
+    fn wallet() -> LocalWallet {
+        "0x1111111111111111111111111111111111111111111111111111111111111111".parse().unwrap()
+    }
+
+    #[test]
+    fn test_tx_type_mapping() {
+        for ty in [0x00, 0x01, 0x02, 0x42, 0x43, 0x50] {
+            assert_eq!(u8::from(SuaveTxType::try_from(ty).unwrap()), ty);
+        }
+        assert!(SuaveTxType::try_from(0x03).is_err());
+        assert!(SuaveTxType::try_from(0x04).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_envelope_legacy_roundtrip() -> Result<()> {
+        let tx = TxLegacy {
+            chain_id: None,
+            nonce: 1,
+            gas_price: 0x3b9aca00,
+            gas_limit: 21000,
+            to: TxKind::Call(Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?),
+            value: U256::from(1),
+            input: Bytes::new(),
+        };
+        let wallet = wallet();
+        let signature = wallet.sign_hash(&tx.signature_hash()).await?;
+        let envelope = SuaveTypedTransaction::from(tx).into_signed(signature);
+
+        let mut encoded = Vec::new();
+        envelope.encode_2718(&mut encoded);
+        assert_eq!(encoded.len(), envelope.encode_2718_len());
+
+        let decoded = SuaveTxEnvelope::decode_2718(&mut encoded.as_slice())?;
+        assert_eq!(decoded.tx_type(), SuaveTxType::Legacy);
+        assert_eq!(decoded, envelope);
+        assert_eq!(decoded.recover_signer()?, wallet.address());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_envelope_eip1559_roundtrip() -> Result<()> {
+        let tx = TxEip1559 {
+            chain_id: 0x067932,
+            nonce: 2,
+            gas_limit: 21000,
+            max_fee_per_gas: 0x3b9aca00,
+            max_priority_fee_per_gas: 0x3b9aca00,
+            to: TxKind::Call(Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?),
+            ..TxEip1559::default()
+        };
+        let wallet = wallet();
+        let signature = wallet.sign_hash(&tx.signature_hash()).await?;
+        let envelope = SuaveTypedTransaction::from(tx).into_signed(signature);
+
+        let mut encoded = Vec::new();
+        envelope.encode_2718(&mut encoded);
+        assert_eq!(encoded[0], 0x02);
+        assert_eq!(encoded.len(), envelope.encode_2718_len());
+
+        let decoded = SuaveTxEnvelope::decode_2718(&mut encoded.as_slice())?;
+        assert_eq!(decoded.tx_type(), SuaveTxType::Eip1559);
+        assert_eq!(decoded.recover_signer()?, wallet.address());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_envelope_confidential_roundtrip() -> Result<()> {
+        let record = ConfidentialComputeRecord {
+            nonce: Some(0x22),
+            to: Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?,
+            gas: Some(0x0f4240),
+            gas_price: Some(0x3b9aca00),
+            kettle_address: Some(devnet_kettle()),
+            chain_id: Some(0x067932),
+            ..ConfidentialComputeRecord::default()
+        };
+        let request = ConfidentialComputeRequest::new(record, Some(ConfidentialBytes::from(b"confidential")));
+        let wallet = wallet();
+
+        for unsigned in [
+            SuaveTypedTransaction::from(UnsignedConfidentialComputeRequest::try_from(request.clone())?),
+            SuaveTypedTransaction::from(UnsignedConfidentialComputeRecord::try_from(request.confidential_compute_record.clone())?),
+        ] {
+            let signature_hash = match &unsigned {
+                SuaveTypedTransaction::ConfidentialComputeRequest(tx) => tx.signature_hash(),
+                SuaveTypedTransaction::ConfidentialComputeRecord(tx) => tx.signature_hash(),
+                _ => unreachable!(),
+            };
+            let signature = wallet.sign_hash(&signature_hash).await?;
+            let envelope = unsigned.into_signed(signature);
+
+            let mut encoded = Vec::new();
+            envelope.encode_2718(&mut encoded);
+            let decoded = SuaveTxEnvelope::decode_2718(&mut encoded.as_slice())?;
+            assert_eq!(decoded.tx_type(), envelope.tx_type());
+            assert!(decoded.is_confidential());
+            assert_eq!(decoded.recover_signer()?, wallet.address());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_envelope_suave_transaction_roundtrip() -> Result<()> {
+        let mut record = ConfidentialComputeRecord {
+            nonce: Some(0x22),
+            to: Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee")?,
+            gas: Some(0x0f4240),
+            gas_price: Some(0x3b9aca00),
+            kettle_address: Some(devnet_kettle()),
+            chain_id: Some(0x067932),
+            ..ConfidentialComputeRecord::default()
+        };
+        record.set_confidential_inputs_hash_from_inputs(&Bytes::new());
+        let signature = wallet().sign_hash(record.signature_hash()?.as_b256()).await?;
+        record.set_sig(signature);
+
+        let kettle: LocalWallet = "0x2222222222222222222222222222222222222222222222222222222222222222".parse()?;
+        let mut suave_tx = SuaveTransaction::new(record, Bytes::from_static(&[1]), 0x067932, signature)?;
+        suave_tx.set_signature(kettle.sign_hash(&suave_tx.signature_hash()).await?);
+        let envelope = SuaveTxEnvelope::from(suave_tx.clone());
+
+        let mut encoded = Vec::new();
+        envelope.encode_2718(&mut encoded);
+        let decoded = SuaveTxEnvelope::decode_2718(&mut encoded.as_slice())?;
+        assert_eq!(decoded.tx_type(), SuaveTxType::SuaveTransaction);
+        assert!(!decoded.is_confidential());
+        assert_eq!(decoded.recover_signer()?, kettle.address());
+
+        let signature = *suave_tx.signature();
+        let typed = SuaveTypedTransaction::from(decoded.clone());
+        assert_eq!(typed, SuaveTypedTransaction::SuaveTransaction(Box::new(suave_tx)));
+        assert_eq!(typed.into_signed(signature), decoded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_envelope_unexpected_type() {
+        let err = SuaveTxEnvelope::decode_2718(&mut [0x03_u8, 0xc0].as_slice()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("unexpected tx type"));
+    }
+
+    fn assert_encoded_len(envelope: &SuaveTxEnvelope) -> Result<(), TestCaseError> {
+        let mut encoded = Vec::new();
+        envelope.encode_2718(&mut encoded);
+        prop_assert_eq!(envelope.encode_2718_len(), encoded.len());
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_encoded_lengths(
+            nonce in arb_u64(),
+            gas_limit in arb_u128(),
+            gas_price in arb_u128(),
+            to in arb_address(),
+            value in arb_u256(),
+            input in arb_bytes(),
+            // EIP-155 folds the chain id into `v`, so keep it clear of overflow.
+            legacy_chain_id in proptest::option::of(0..=u64::from(u32::MAX)),
+            chain_id in arb_u64(),
+            signature in arb_signature(),
+        ) {
+            let legacy = TxLegacy {
+                chain_id: legacy_chain_id,
+                nonce,
+                gas_price,
+                gas_limit,
+                to: TxKind::Call(to),
+                value,
+                input: input.clone(),
+            };
+            assert_encoded_len(&SuaveTypedTransaction::from(legacy).into_signed(signature))?;
+
+            let eip1559 = TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas: gas_price,
+                max_priority_fee_per_gas: gas_price,
+                to: TxKind::Call(to),
+                value,
+                input: input.clone(),
+                ..TxEip1559::default()
+            };
+            assert_encoded_len(&SuaveTypedTransaction::from(eip1559).into_signed(signature))?;
+        }
+    }
+
+}
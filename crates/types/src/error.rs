@@ -0,0 +1,88 @@
+use core::fmt;
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{B256, SignatureError};
+
+
+pub type Result<T, E = SuaveTypesError> = core::result::Result<T, E>;
+
+/// Errors produced while building, encoding or decoding SUAVE types.
+#[derive(Debug)]
+pub enum SuaveTypesError {
+    /// A field required for encoding or hashing is not set.
+    MissingField(&'static str),
+    /// A field value cannot be represented in the target encoding.
+    InvalidField(&'static str),
+    /// Fields are set that the target type has no equivalent for.
+    UnsupportedFields(Vec<&'static str>),
+    /// The signature is malformed or its parity is invalid.
+    InvalidSignature(SignatureError),
+    /// The confidential inputs hash does not match the confidential inputs.
+    ConfidentialInputsHashMismatch { expected: B256, found: B256 },
+    /// The EIP-2718 type byte is not the one expected.
+    UnexpectedTxType(u8),
+    /// The confidential call response is missing data or could not be parsed.
+    MalformedResponse(String),
+    /// The confidential inputs don't encode or decode as the requested type.
+    MalformedConfidentialInputs(String),
+}
+
+impl fmt::Display for SuaveTypesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuaveTypesError::MissingField(field) => write!(f, "missing {field} field"),
+            SuaveTypesError::InvalidField(field) => write!(f, "invalid {field} field"),
+            SuaveTypesError::UnsupportedFields(fields) => write!(f, "unsupported fields: {}", fields.join(", ")),
+            SuaveTypesError::InvalidSignature(err) => write!(f, "invalid signature: {err}"),
+            SuaveTypesError::ConfidentialInputsHashMismatch { expected, found } => write!(
+                f,
+                "confidential inputs hash mismatch: expected {expected}, found {found}"
+            ),
+            SuaveTypesError::UnexpectedTxType(ty) => write!(f, "unexpected tx type: {ty:#04x}"),
+            SuaveTypesError::MalformedResponse(reason) => write!(f, "malformed response: {reason}"),
+            SuaveTypesError::MalformedConfidentialInputs(reason) => write!(f, "malformed confidential inputs: {reason}"),
+        }
+    }
+}
+
+/// Fails with every field in `fields` that is set.
+#[cfg(feature = "rpc")]
+pub(crate) fn reject_unsupported<const N: usize>(fields: [(&'static str, bool); N]) -> Result<()> {
+    let unsupported: Vec<_> = fields.into_iter().filter_map(|(field, set)| set.then_some(field)).collect();
+    if unsupported.is_empty() {
+        return Ok(());
+    }
+    Err(SuaveTypesError::UnsupportedFields(unsupported))
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SuaveTypesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SuaveTypesError::InvalidSignature(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<SignatureError> for SuaveTypesError {
+    fn from(err: SignatureError) -> Self {
+        SuaveTypesError::InvalidSignature(err)
+    }
+}
+
+#[cfg(feature = "rlp")]
+impl From<SuaveTypesError> for alloy_rlp::Error {
+    fn from(err: SuaveTypesError) -> Self {
+        let msg = match err {
+            SuaveTypesError::MissingField(_) => "missing field",
+            SuaveTypesError::InvalidField(_) => "invalid field",
+            SuaveTypesError::UnsupportedFields(_) => "unsupported fields",
+            SuaveTypesError::InvalidSignature(_) => "invalid signature",
+            SuaveTypesError::ConfidentialInputsHashMismatch { .. } => "confidential inputs hash mismatch",
+            SuaveTypesError::UnexpectedTxType(_) => "unexpected tx type",
+            SuaveTypesError::MalformedResponse(_) => "malformed response",
+            SuaveTypesError::MalformedConfidentialInputs(_) => "malformed confidential inputs",
+        };
+        alloy_rlp::Error::Custom(msg)
+    }
+}
@@ -0,0 +1,248 @@
+//! `Arbitrary` impls and proptest strategies for fuzzing.
+//!
+//! Generated values are always valid: every encoded field is set, requests commit to their
+//! confidential inputs and signed values carry real signatures by one of
+//! [`ARBITRARY_SIGNER_KEYS`]. The `arbitrary` impls seed the same strategies from the fuzzer input.
+
+use alloy_primitives::{Address, Bytes, Signature, B256, U256};
+use k256::ecdsa::SigningKey;
+use proptest::{
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+use super::crecord::{ConfidentialComputeRecord, SignedConfidentialComputeRecord};
+use super::crequest::{ConfidentialComputeRequest, SignedConfidentialComputeRequest};
+#[cfg(feature = "rpc")]
+use super::cresponse::ConfidentialCallResponse;
+use super::spec::SuaveSpec;
+use super::suavetx::SuaveTransaction;
+
+
+/// Private keys of the senders and kettles of generated values.
+pub const ARBITRARY_SIGNER_KEYS: [B256; 4] = [
+    B256::repeat_byte(0x11),
+    B256::repeat_byte(0x22),
+    B256::repeat_byte(0x33),
+    B256::repeat_byte(0x44),
+];
+
+// Values up to 0x80 hit the single-byte and empty-string RLP edge cases.
+pub(crate) fn arb_u64() -> impl Strategy<Value = u64> {
+    prop_oneof![0..=0x80_u64, any::<u64>()]
+}
+
+pub(crate) fn arb_u128() -> impl Strategy<Value = u128> {
+    prop_oneof![0..=0x80_u128, any::<u64>().prop_map(u128::from), any::<u128>()]
+}
+
+pub(crate) fn arb_u256() -> impl Strategy<Value = U256> {
+    prop_oneof![(0..=0x80_u64).prop_map(U256::from), any::<[u8; 32]>().prop_map(U256::from_be_bytes)]
+}
+
+pub(crate) fn arb_address() -> impl Strategy<Value = Address> {
+    any::<[u8; 20]>().prop_map(Address::from)
+}
+
+// Crosses the 55-byte short-string and two-byte length header boundaries.
+pub(crate) fn arb_bytes() -> impl Strategy<Value = Bytes> {
+    prop::collection::vec(any::<u8>(), 0..600).prop_map(Bytes::from)
+}
+
+fn arb_chain_id() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(SuaveSpec::RIGIL_CHAIN_ID), Just(SuaveSpec::TOLIMAN_CHAIN_ID), arb_u64()]
+}
+
+fn arb_signer() -> impl Strategy<Value = SigningKey> {
+    prop::sample::select(&ARBITRARY_SIGNER_KEYS[..])
+        .prop_map(|key| SigningKey::from_slice(key.as_slice()).expect("valid private key"))
+}
+
+/// Unsigned record with every encoded field set; `is_eip712` only on chains that support it.
+fn arb_record() -> impl Strategy<Value = ConfidentialComputeRecord> {
+    (
+        arb_u64(),
+        arb_u64(),
+        arb_u128(),
+        arb_address(),
+        arb_u256(),
+        arb_bytes(),
+        arb_address(),
+        arb_chain_id(),
+        any::<[u8; 32]>().prop_map(B256::from),
+        any::<bool>(),
+    ).prop_map(|(nonce, gas, gas_price, to, value, input, kettle_address, chain_id, cinputs_hash, is_eip712)| {
+        ConfidentialComputeRecord {
+            nonce: Some(nonce),
+            to,
+            gas: Some(gas.into()),
+            gas_price: Some(gas_price),
+            value,
+            input,
+            kettle_address: Some(kettle_address),
+            chain_id: Some(chain_id),
+            confidential_inputs_hash: Some(cinputs_hash),
+            is_eip712: is_eip712 && SuaveSpec::from_chain_id(chain_id).eip712_records,
+            signature: None,
+            from: None,
+        }
+    })
+}
+
+fn arb_request() -> impl Strategy<Value = ConfidentialComputeRequest> {
+    (arb_record(), arb_bytes())
+        .prop_map(|(record, cinputs)| ConfidentialComputeRequest::new(record, Some(cinputs.into())))
+}
+
+fn sign(key: &SigningKey, hash: &B256) -> Signature {
+    let (signature, recovery_id) = key.sign_prehash_recoverable(hash.as_slice()).expect("valid prehash");
+    Signature::from((signature, recovery_id))
+}
+
+// Sets `from` too, as decoding does.
+fn sign_record(mut record: ConfidentialComputeRecord, key: &SigningKey) -> ConfidentialComputeRecord {
+    let hash = record.signature_hash().expect("record fields are set");
+    record.signature = Some(sign(key, hash.as_b256()));
+    record.from = Some(Address::from_private_key(key));
+    record
+}
+
+fn arb_signed_record() -> impl Strategy<Value = SignedConfidentialComputeRecord> {
+    (arb_record(), arb_signer()).prop_map(|(record, key)| {
+        SignedConfidentialComputeRecord::try_from(sign_record(record, &key)).expect("record is signed")
+    })
+}
+
+fn arb_signed_request() -> impl Strategy<Value = SignedConfidentialComputeRequest> {
+    (arb_request(), arb_signer()).prop_map(|(mut ccr, key)| {
+        ccr.confidential_compute_record = sign_record(ccr.confidential_compute_record, &key);
+        SignedConfidentialComputeRequest::try_from(ccr).expect("request is signed")
+    })
+}
+
+/// 0x50 transaction of a signed record, signed by the record's kettle.
+fn arb_suave_transaction() -> impl Strategy<Value = SuaveTransaction> {
+    (arb_record(), arb_signer(), arb_signer(), arb_bytes()).prop_map(|(mut record, sender, kettle, result)| {
+        record.kettle_address = Some(Address::from_private_key(&kettle));
+        let record = sign_record(record, &sender);
+        let chain_id = record.chain_id.expect("chain id is set");
+        let placeholder = record.signature.expect("record is signed");
+        let suave_tx = SuaveTransaction::new(record, result, chain_id, placeholder).expect("record is complete");
+        let signature = sign(&kettle, &suave_tx.signature_hash());
+        suave_tx.with_signature(signature)
+    })
+}
+
+#[cfg(feature = "rpc")]
+fn arb_response() -> impl Strategy<Value = ConfidentialCallResponse> {
+    arb_suave_transaction().prop_map(|suave_tx| {
+        ConfidentialCallResponse::try_from(suave_tx).expect("transaction is signed")
+    })
+}
+
+macro_rules! impl_arbitrary {
+    ($($ty:ty => $strategy:expr),* $(,)?) => {$(
+        impl proptest::arbitrary::Arbitrary for $ty {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                $strategy.boxed()
+            }
+        }
+
+        impl<'a> arbitrary::Arbitrary<'a> for $ty {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                generate(u, any::<$ty>())
+            }
+        }
+    )*};
+}
+
+impl_arbitrary! {
+    ConfidentialComputeRecord => arb_record(),
+    SignedConfidentialComputeRecord => arb_signed_record(),
+    ConfidentialComputeRequest => arb_request(),
+    SignedConfidentialComputeRequest => arb_signed_request(),
+    SuaveTransaction => arb_suave_transaction(),
+}
+
+#[cfg(feature = "rpc")]
+impl_arbitrary! {
+    ConfidentialCallResponse => arb_response(),
+}
+
+// The strategies never reject, so a single draw from an RNG seeded by the input is enough.
+fn generate<T: Strategy>(u: &mut arbitrary::Unstructured<'_>, strategy: T) -> arbitrary::Result<T::Value> {
+    let seed: [u8; 32] = u.arbitrary()?;
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed);
+    let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+    let tree = strategy.new_tree(&mut runner).expect("strategy doesn't reject");
+    Ok(tree.current())
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+    use arbitrary::{Arbitrary, Unstructured};
+    use super::*;
+
+    #[test]
+    fn test_arbitrary_is_deterministic() {
+        let data = [0xab; 64];
+        let first = SignedConfidentialComputeRequest::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let second = SignedConfidentialComputeRequest::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(first, second);
+        let signers = ARBITRARY_SIGNER_KEYS.map(|key| Address::from_private_key(&SigningKey::from_slice(key.as_slice()).unwrap()));
+        assert!(signers.contains(&first.recover_signer().unwrap()));
+
+        let response = ConfidentialCallResponse::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!(response.request_record.recover_signer().ok(), response.request_record.from);
+    }
+
+    proptest! {
+        #[test]
+        fn test_record_roundtrip(record in any::<ConfidentialComputeRecord>(), signed in any::<SignedConfidentialComputeRecord>()) {
+            let json = serde_json::to_string(&record).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ConfidentialComputeRecord>(&json).unwrap(), record);
+
+            let encoded = signed.encoded_2718();
+            let record = signed.clone().into_record();
+            prop_assert_eq!(&encoded[..], &record.rlp_encode().unwrap()[..]);
+            prop_assert_eq!(SignedConfidentialComputeRecord::decode_2718(&mut encoded.as_slice()).unwrap(), signed);
+            let json = serde_json::to_string(&record).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ConfidentialComputeRecord>(&json).unwrap(), record);
+        }
+
+        #[test]
+        fn test_request_roundtrip(ccr in any::<ConfidentialComputeRequest>(), signed in any::<SignedConfidentialComputeRequest>()) {
+            prop_assert!(ccr.validate().is_valid());
+            let json = serde_json::to_string(&ccr).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ConfidentialComputeRequest>(&json).unwrap(), ccr);
+
+            let encoded = signed.encoded_2718();
+            prop_assert_eq!(&encoded[..], &signed.rlp_encode()[..]);
+            prop_assert_eq!(SignedConfidentialComputeRequest::decode_2718(&mut encoded.as_slice()).unwrap(), signed.clone());
+            let ccr = ConfidentialComputeRequest::from(signed);
+            prop_assert!(ccr.validate().is_valid());
+            let json = serde_json::to_string(&ccr).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ConfidentialComputeRequest>(&json).unwrap(), ccr);
+        }
+
+        #[test]
+        fn test_response_roundtrip(response in any::<ConfidentialCallResponse>()) {
+            let suave_tx = SuaveTransaction::try_from(&response).unwrap();
+            prop_assert_eq!(suave_tx.recover_signer().ok(), response.request_record.kettle_address);
+            let encoded = suave_tx.encoded_2718();
+            prop_assert_eq!(&encoded[..], &suave_tx.rlp_encode()[..]);
+            prop_assert_eq!(SuaveTransaction::decode_2718(&mut encoded.as_slice()).unwrap(), suave_tx.clone());
+            let json = serde_json::to_string(&suave_tx).unwrap();
+            prop_assert_eq!(serde_json::from_str::<SuaveTransaction>(&json).unwrap(), suave_tx);
+
+            let json = serde_json::to_string(&response).unwrap();
+            prop_assert_eq!(serde_json::from_str::<ConfidentialCallResponse>(&json).unwrap(), response);
+        }
+    }
+
+}
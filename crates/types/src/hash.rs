@@ -0,0 +1,152 @@
+use core::fmt;
+use alloy_primitives::B256;
+
+
+// Wraps a `B256` so that hashes with different preimages can't be mixed up.
+macro_rules! hash_newtype {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        pub struct $name(pub B256);
+
+        impl $name {
+            pub const fn new(hash: B256) -> Self {
+                Self(hash)
+            }
+
+            pub const fn as_b256(&self) -> &B256 {
+                &self.0
+            }
+
+            pub const fn into_b256(self) -> B256 {
+                self.0
+            }
+        }
+
+        impl From<B256> for $name {
+            fn from(hash: B256) -> Self {
+                Self(hash)
+            }
+        }
+
+        impl From<$name> for B256 {
+            fn from(hash: $name) -> Self {
+                hash.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                self.0.as_ref()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+hash_newtype!(
+    /// Hash the sender signs for a confidential compute record: the 0x42-prefixed RLP hash
+    /// of the request fields, or the EIP-712 hash when `is_eip712` is set.
+    RecordSigningHash
+);
+
+hash_newtype!(
+    /// Transaction hash suave-geth assigns to a confidential compute request: the hash of its
+    /// signed record's 0x42 encoding, reported as `requestRecord.hash`.
+    RequestHash
+);
+
+hash_newtype!(
+    /// Transaction hash of a kettle-signed 0x50 transaction, as reported by `eth_getTransactionByHash`.
+    SuaveTransactionHash
+);
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use std::str::FromStr;
+    use alloy::{
+        consensus::SignableTransaction,
+        primitives::Bytes,
+        signers::{wallet::LocalWallet, Signer},
+    };
+    use eyre::Result;
+    use super::*;
+    use crate::{
+        ConfidentialComputeRequest, SignedConfidentialComputeRecord, SignedConfidentialComputeRequest,
+        SuaveTransaction, UnsignedConfidentialComputeRequest,
+    };
+    use crate::test_utils::{devnet_record, response, sender, RESPONSE};
+
+    #[test]
+    fn test_record_signing_hash_vectors() -> Result<()> {
+        let record = devnet_record();
+        let expected = RecordSigningHash::from(B256::from_str("0x45971e29624eb49bc8144a82cf2eab047d5254a545625bdb6d2ec115262ecce6")?);
+        assert_eq!(record.signature_hash()?, expected);
+        assert_eq!(record.recover_signer()?, sender());
+
+        let record = response().request_record;
+        let expected = RecordSigningHash::from(B256::from_str("0xe5571e5ea305cca8918f527779b381e3f786fe8258287d1e0a3c5ce0b32724c6")?);
+        assert_eq!(record.signature_hash()?, expected);
+        assert_eq!(record.recover_signer()?, sender());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_hash_vectors() -> Result<()> {
+        let json: serde_json::Value = serde_json::from_str(RESPONSE)?;
+        let expected: RequestHash = serde_json::from_value(json["requestRecord"]["hash"].clone())?;
+        let record = response().request_record;
+        assert_eq!(record.request_hash()?, expected);
+        assert_ne!(record.signature_hash()?.into_b256(), expected.into_b256());
+
+        let signed_record = SignedConfidentialComputeRecord::try_from(record.clone())?;
+        assert_eq!(signed_record.hash(), expected);
+        let ccr = ConfidentialComputeRequest::from_record(record.clone())?;
+        let signed_request = SignedConfidentialComputeRequest::try_from(ccr.clone())?;
+        assert_eq!(signed_request.hash(), expected);
+        let unsigned = UnsignedConfidentialComputeRequest::try_from(ccr)?;
+        let signed = unsigned.into_signed(record.signature.unwrap());
+        assert_eq!(RequestHash::from(*signed.hash()), expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_suave_transaction_hash_vectors() -> Result<()> {
+        let expected = SuaveTransactionHash::from(B256::from_str("0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2")?);
+        assert_eq!(response().hash(), expected);
+        // Rigil records are encoded without the `is_eip712` flag.
+        assert_eq!(SuaveTransaction::try_from(&response())?.hash(), expected);
+
+        let kettle: LocalWallet = "0x2222222222222222222222222222222222222222222222222222222222222222".parse()?;
+        let record = devnet_record();
+        let signature = record.signature.unwrap();
+        let mut suave_tx = SuaveTransaction::new(record, Bytes::from_static(&[1]), 0x067932, signature)?;
+        suave_tx.set_signature(kettle.sign_hash(&suave_tx.signature_hash()).await?);
+        let expected = SuaveTransactionHash::from(B256::from_str("0x8c783c2cb2b5e86bb1b2a407d23d52ee1299afa8dc7910021be631a90a4972b8")?);
+        assert_eq!(suave_tx.hash(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_transparent() -> Result<()> {
+        let hash = RequestHash::from(B256::repeat_byte(0x42));
+        let json = serde_json::to_string(&hash)?;
+        assert_eq!(json, serde_json::to_string(&B256::repeat_byte(0x42))?);
+        assert_eq!(serde_json::from_str::<RequestHash>(&json)?, hash);
+        assert_eq!(hash.to_string(), B256::repeat_byte(0x42).to_string());
+
+        Ok(())
+    }
+
+}
@@ -0,0 +1,344 @@
+//! JSON format suave-geth uses for SUAVE transactions, its `txJSON` object.
+//!
+//! Records (0x42), requests (0x43) and kettle-signed transactions (0x50) share one layout and are
+//! told apart by `type`. Quantities are written as minimal `0x` hex and read from hex, decimal
+//! strings or numbers. `nonce`, `to`, `gas`, `gasPrice`, `value`, `input` and `v`/`r`/`s` are
+//! always present and may be `null`, as in suave-geth; records also carry the fee-market fields.
+//! A 0x50 transaction repeats its record's fields at the top level, with the result as `input`,
+//! and moves the record's `from` there. Unsigned records carry a zero signature. `hash` is written whenever
+//! the transaction can be encoded, and checked when read.
+
+use alloy_primitives::{keccak256, Address, Bytes, Signature, B256, U128, U256, U64};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use super::cbytes::ConfidentialBytes;
+use super::crecord::{signature_to_vrs, ConfidentialComputeRecord};
+use super::crequest::ConfidentialComputeRequest;
+use super::error::{Result, SuaveTypesError};
+use super::suavetx::SuaveTransaction;
+
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SuaveTxJson {
+    #[serde(rename = "type")]
+    ty: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chain_id: Option<U64>,
+    nonce: Option<U64>,
+    to: Option<Address>,
+    gas: Option<U128>,
+    gas_price: Option<U128>,
+    // `Some(None)` is written as `null`; 0x50 transactions leave the fields out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_priority_fee_per_gas: Option<Option<U128>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_fee_per_gas: Option<Option<U128>>,
+    value: Option<U256>,
+    input: Option<Bytes>,
+    v: Option<U64>,
+    r: Option<U256>,
+    s: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y_parity: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kettle_address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidential_inputs_hash: Option<B256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidential_inputs: Option<ConfidentialBytes>,
+    #[serde(rename = "isEIP712", alias = "isEip712", skip_serializing_if = "Option::is_none")]
+    is_eip712: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_record: Option<Box<SuaveTxJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidential_compute_result: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<B256>,
+}
+
+impl SuaveTxJson {
+
+    fn check_type(&self, ty: u8) -> Result<()> {
+        match self.ty {
+            Some(found) if found != U64::from(ty) => Err(SuaveTypesError::UnexpectedTxType(found.saturating_to())),
+            _ => Ok(()),
+        }
+    }
+
+    /// `None` for a missing or zero signature, which suave-geth writes for unsigned records.
+    fn signature(&self) -> Result<Option<Signature>> {
+        let (r, s) = (self.r.unwrap_or_default(), self.s.unwrap_or_default());
+        if r.is_zero() && s.is_zero() {
+            return Ok(None);
+        }
+        let v = self.v.or(self.y_parity).ok_or(SuaveTypesError::MissingField("v"))?;
+        Ok(Some(Signature::from_rs_and_parity(r, s, v.saturating_to::<u64>())?))
+    }
+
+    fn set_signature(&mut self, signature: Option<Signature>) {
+        let (v, r, s) = signature.map_or((0, U256::ZERO, U256::ZERO), signature_to_vrs);
+        self.v = Some(U64::from(v));
+        self.r = Some(r);
+        self.s = Some(s);
+    }
+
+    fn into_record(self) -> Result<ConfidentialComputeRecord> {
+        if self.max_priority_fee_per_gas.flatten().is_some() {
+            return Err(SuaveTypesError::InvalidField("max_priority_fee_per_gas"));
+        }
+        if self.max_fee_per_gas.flatten().is_some() {
+            return Err(SuaveTypesError::InvalidField("max_fee_per_gas"));
+        }
+        Ok(ConfidentialComputeRecord {
+            signature: self.signature()?,
+            nonce: self.nonce.map(|nonce| nonce.to()),
+            to: self.to.ok_or(SuaveTypesError::MissingField("to"))?,
+            gas: self.gas.map(|gas| gas.to()),
+            gas_price: self.gas_price.map(|gas_price| gas_price.to()),
+            value: self.value.ok_or(SuaveTypesError::MissingField("value"))?,
+            input: self.input.ok_or(SuaveTypesError::MissingField("input"))?,
+            kettle_address: self.kettle_address,
+            chain_id: self.chain_id.map(|chain_id| chain_id.to()),
+            confidential_inputs_hash: self.confidential_inputs_hash,
+            is_eip712: self.is_eip712.unwrap_or_default(),
+            from: self.from,
+        })
+    }
+
+}
+
+impl From<&ConfidentialComputeRecord> for SuaveTxJson {
+    fn from(record: &ConfidentialComputeRecord) -> Self {
+        let mut json = Self {
+            ty: Some(U64::from(ConfidentialComputeRecord::TYPE)),
+            chain_id: record.chain_id.map(U64::from),
+            nonce: record.nonce.map(U64::from),
+            to: Some(record.to),
+            gas: record.gas.map(U128::from),
+            gas_price: record.gas_price.map(U128::from),
+            max_priority_fee_per_gas: Some(None),
+            max_fee_per_gas: Some(None),
+            value: Some(record.value),
+            input: Some(record.input.clone()),
+            kettle_address: record.kettle_address,
+            confidential_inputs_hash: record.confidential_inputs_hash,
+            is_eip712: (record.is_eip712 || record.spec().eip712_records).then_some(record.is_eip712),
+            from: record.from,
+            hash: record.rlp_encode().ok().map(keccak256),
+            ..Default::default()
+        };
+        json.set_signature(record.signature);
+        json
+    }
+}
+
+impl TryFrom<SuaveTxJson> for ConfidentialComputeRecord {
+    type Error = SuaveTypesError;
+
+    fn try_from(json: SuaveTxJson) -> Result<Self> {
+        json.check_type(ConfidentialComputeRecord::TYPE)?;
+        let hash = json.hash;
+        let record = json.into_record()?;
+        check_hash(hash, record.rlp_encode().ok().map(keccak256))?;
+        Ok(record)
+    }
+}
+
+impl From<&ConfidentialComputeRequest> for SuaveTxJson {
+    fn from(ccr: &ConfidentialComputeRequest) -> Self {
+        Self {
+            ty: Some(U64::from(ConfidentialComputeRequest::TYPE)),
+            confidential_inputs: Some(ccr.confidential_inputs.clone()),
+            hash: ccr.request_hash().ok().map(|hash| hash.into_b256()),
+            ..Self::from(&ccr.confidential_compute_record)
+        }
+    }
+}
+
+impl TryFrom<SuaveTxJson> for ConfidentialComputeRequest {
+    type Error = SuaveTypesError;
+
+    // `type` is required so that requests can be told apart from Ethereum transaction requests.
+    fn try_from(mut json: SuaveTxJson) -> Result<Self> {
+        if json.ty.is_none() {
+            return Err(SuaveTypesError::MissingField("type"));
+        }
+        json.check_type(ConfidentialComputeRequest::TYPE)?;
+        let hash = json.hash;
+        let confidential_inputs = json.confidential_inputs.take().unwrap_or_default();
+        let confidential_compute_record = json.into_record()?;
+        if let Some(expected) = confidential_compute_record.confidential_inputs_hash {
+            let found = confidential_inputs.hash();
+            if expected != found {
+                return Err(SuaveTypesError::ConfidentialInputsHashMismatch { expected, found });
+            }
+        }
+        let ccr = Self { confidential_compute_record, confidential_inputs };
+        check_hash(hash, ccr.request_hash().ok().map(|hash| hash.into_b256()))?;
+        Ok(ccr)
+    }
+}
+
+/// `from` is the sender recovered from the record signature, so it is only written with `k256`.
+impl From<&SuaveTransaction> for SuaveTxJson {
+    fn from(suave_tx: &SuaveTransaction) -> Self {
+        let record = suave_tx.request_record().clone().into_record();
+        let request_record = Self { from: None, ..Self::from(&record) };
+        let mut json = Self {
+            ty: Some(U64::from(SuaveTransaction::TYPE)),
+            chain_id: Some(U64::from(suave_tx.chain_id())),
+            nonce: record.nonce.map(U64::from),
+            to: Some(record.to),
+            gas: record.gas.map(U128::from),
+            gas_price: record.gas_price.map(U128::from),
+            value: Some(record.value),
+            input: Some(suave_tx.confidential_compute_result().clone()),
+            request_record: Some(Box::new(request_record)),
+            confidential_compute_result: Some(suave_tx.confidential_compute_result().clone()),
+            from: record.from,
+            hash: Some(suave_tx.hash().into_b256()),
+            ..Default::default()
+        };
+        json.set_signature(Some(*suave_tx.signature()));
+        json
+    }
+}
+
+impl TryFrom<SuaveTxJson> for SuaveTransaction {
+    type Error = SuaveTypesError;
+
+    fn try_from(json: SuaveTxJson) -> Result<Self> {
+        json.check_type(SuaveTransaction::TYPE)?;
+        let signature = json.signature()?.ok_or(SuaveTypesError::MissingField("signature"))?;
+        let request_record = ConfidentialComputeRecord::try_from(
+            *json.request_record.ok_or(SuaveTypesError::MissingField("request_record"))?
+        )?;
+        let suave_tx = Self::new(
+            request_record,
+            json.confidential_compute_result
+                .ok_or(SuaveTypesError::MissingField("confidential_compute_result"))?,
+            json.chain_id.ok_or(SuaveTypesError::MissingField("chain_id"))?.to(),
+            signature,
+        )?;
+        check_hash(json.hash, Some(suave_tx.hash().into_b256()))?;
+        Ok(suave_tx)
+    }
+}
+
+/// Checks a hash read from JSON against the one computed from the decoded transaction, if both are known.
+fn check_hash(expected: Option<B256>, found: Option<B256>) -> Result<()> {
+    match (expected, found) {
+        (Some(expected), Some(found)) if expected != found => Err(SuaveTypesError::InvalidField("hash")),
+        _ => Ok(()),
+    }
+}
+
+macro_rules! impl_serde_via_json {
+    ($($ty:ty),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+                SuaveTxJson::from(self).serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+                SuaveTxJson::deserialize(deserializer)?
+                    .try_into()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+
+impl_serde_via_json!(ConfidentialComputeRecord, ConfidentialComputeRequest, SuaveTransaction);
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use serde_json::{json, Value};
+    use super::*;
+    use crate::ConfidentialCallResponse;
+    use crate::test_utils::RESPONSE;
+
+    fn response() -> (Value, ConfidentialCallResponse) {
+        let json: Value = serde_json::from_str(RESPONSE).unwrap();
+        (json, serde_json::from_str(RESPONSE).unwrap())
+    }
+
+    #[test]
+    fn test_record_matches_suave_geth() {
+        let (json, response) = response();
+        let record: ConfidentialComputeRecord = serde_json::from_value(json["requestRecord"].clone()).unwrap();
+        assert_eq!(record.from, None);
+        assert_eq!(serde_json::to_value(&record).unwrap(), json["requestRecord"]);
+        assert_eq!(record, ConfidentialComputeRecord { from: None, ..response.request_record });
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let (_, response) = response();
+        let ccr = ConfidentialComputeRequest::from_record(response.request_record.clone()).unwrap();
+        let json = serde_json::to_value(&ccr).unwrap();
+        assert_eq!(json["type"], "0x43");
+        assert_eq!(json["confidentialInputs"], "0x");
+        assert_eq!(json["hash"], json!(ccr.request_hash().unwrap()));
+        assert_eq!(json["from"], json!(response.request_record.from));
+        assert_eq!(serde_json::from_value::<ConfidentialComputeRequest>(json.clone()).unwrap(), ccr);
+
+        let mut untyped = json;
+        untyped.as_object_mut().unwrap().remove("type");
+        assert!(serde_json::from_value::<ConfidentialComputeRequest>(untyped).is_err());
+    }
+
+    #[test]
+    fn test_suave_transaction_roundtrip() {
+        let (mut json, response) = response();
+        let suave_tx = SuaveTransaction::try_from(&response).unwrap();
+        let suave_json = serde_json::to_value(&suave_tx).unwrap();
+        // Block fields belong to the RPC response, not to the transaction.
+        for key in ["blockHash", "blockNumber", "transactionIndex"] {
+            json.as_object_mut().unwrap().remove(key);
+        }
+        assert_eq!(suave_json, json);
+        assert_eq!(serde_json::from_value::<SuaveTransaction>(suave_json).unwrap(), suave_tx);
+    }
+
+    #[test]
+    fn test_unsigned_record_roundtrip() {
+        let record = ConfidentialComputeRecord { nonce: Some(1), chain_id: Some(0x067932), ..Default::default() };
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["gas"], Value::Null);
+        assert_eq!((&json["v"], &json["r"], &json["s"]), (&json!("0x0"), &json!("0x0"), &json!("0x0")));
+        assert_eq!(json["isEIP712"], false);
+        assert!(json.get("hash").is_none());
+        assert_eq!(serde_json::from_value::<ConfidentialComputeRecord>(json).unwrap(), record);
+    }
+
+    #[test]
+    fn test_record_quirks() {
+        let (json, response) = response();
+        let record = ConfidentialComputeRecord { from: None, ..response.request_record };
+
+        // Decimal strings and numbers are read as quantities.
+        let mut decimal = json["requestRecord"].clone();
+        decimal["gas"] = json!("1000000");
+        decimal["nonce"] = json!(0x45);
+        assert_eq!(serde_json::from_value::<ConfidentialComputeRecord>(decimal).unwrap(), record);
+
+        let mut wrong_hash = json["requestRecord"].clone();
+        wrong_hash["hash"] = json!(B256::ZERO);
+        assert!(serde_json::from_value::<ConfidentialComputeRecord>(wrong_hash).is_err());
+
+        let mut fee_market = json["requestRecord"].clone();
+        fee_market["maxFeePerGas"] = json!("0x1");
+        assert!(serde_json::from_value::<ConfidentialComputeRecord>(fee_market).is_err());
+
+        let mut wrong_type = json["requestRecord"].clone();
+        wrong_type["type"] = json!("0x43");
+        assert!(serde_json::from_value::<ConfidentialComputeRecord>(wrong_type).is_err());
+    }
+
+}
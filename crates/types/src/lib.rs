@@ -1,7 +1,61 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod cbytes;
 mod crecord;
 mod crequest;
+#[cfg(feature = "rpc")]
 mod cresponse;
+mod eip712;
+#[cfg(feature = "consensus")]
+mod envelope;
+mod error;
+#[cfg(any(feature = "arbitrary", all(test, feature = "rpc")))]
+mod fuzz;
+mod hash;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "rpc")]
+mod receipt;
+mod spec;
+#[cfg(feature = "rlp")]
+mod suavetx;
+#[cfg(all(test, feature = "rpc"))]
+mod test_utils;
+#[cfg(feature = "rpc")]
+mod txresponse;
+mod validate;
+#[cfg(feature = "rpc")]
+mod verify;
+#[cfg(any(feature = "test-vectors", all(test, feature = "rpc")))]
+mod vectors;
 
-pub use crecord::ConfidentialComputeRecord;
-pub use crequest::ConfidentialComputeRequest;
-pub use cresponse::ConfidentialCallResponse;
\ No newline at end of file
+pub use cbytes::ConfidentialBytes;
+pub use crecord::{ConfidentialComputeRecord, UnsignedConfidentialComputeRecord};
+#[cfg(feature = "rlp")]
+pub use crecord::SignedConfidentialComputeRecord;
+pub use crequest::{ConfidentialComputeRequest, UnsignedConfidentialComputeRequest};
+#[cfg(feature = "rlp")]
+pub use crequest::SignedConfidentialComputeRequest;
+#[cfg(feature = "rpc")]
+pub use cresponse::ConfidentialCallResponse;
+pub use eip712::{ConfidentialRecord, EIP712_DOMAIN_NAME};
+#[cfg(feature = "consensus")]
+pub use envelope::{SuaveTxEnvelope, SuaveTxType, SuaveTypedTransaction};
+pub use error::SuaveTypesError;
+#[cfg(feature = "arbitrary")]
+pub use fuzz::ARBITRARY_SIGNER_KEYS;
+pub use hash::{RecordSigningHash, RequestHash, SuaveTransactionHash};
+#[cfg(feature = "rpc")]
+pub use receipt::{SuaveReceiptEnvelope, SuaveTransactionReceipt};
+pub use spec::SuaveSpec;
+#[cfg(feature = "rlp")]
+pub use suavetx::SuaveTransaction;
+#[cfg(feature = "rpc")]
+pub use txresponse::SuaveTransactionResponse;
+pub use validate::{RequestDiagnostics, RequestIssue, Severity, MAX_INPUT_SIZE};
+#[cfg(feature = "rpc")]
+pub use verify::{ChainIdCheck, RequestField, ResponseVerdict, SignerCheck};
+#[cfg(feature = "test-vectors")]
+pub use vectors::{TestVector, TestVectors, VectorMismatch};
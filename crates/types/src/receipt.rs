@@ -0,0 +1,328 @@
+use alloy_rlp::{Decodable, Encodable};
+use serde::{Deserialize, Serialize};
+use alloy_consensus::{ReceiptEnvelope, ReceiptWithBloom, TxReceipt};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+#[cfg(feature = "network")]
+use alloy_network::ReceiptResponse;
+#[cfg(feature = "network")]
+use alloy_primitives::Address;
+use alloy_primitives::{Bloom, Log as PrimitiveLog, B256};
+use alloy_rpc_types::eth::{Log, TransactionReceipt};
+use super::cresponse::ConfidentialCallResponse;
+use super::envelope::SuaveTxType;
+use super::error::{Result, SuaveTypesError};
+
+
+/// Receipt envelope for the transaction types mined on SUAVE chains.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SuaveReceiptEnvelope<T = PrimitiveLog> {
+    #[serde(rename = "0x0", alias = "0x00")]
+    Legacy(ReceiptWithBloom<T>),
+    #[serde(rename = "0x1", alias = "0x01")]
+    Eip2930(ReceiptWithBloom<T>),
+    #[serde(rename = "0x2", alias = "0x02")]
+    Eip1559(ReceiptWithBloom<T>),
+    /// Receipt of a kettle-signed [`SuaveTransaction`](crate::SuaveTransaction).
+    #[serde(rename = "0x50")]
+    SuaveTransaction(ReceiptWithBloom<T>),
+}
+
+impl<T> SuaveReceiptEnvelope<T> {
+
+    pub fn tx_type(&self) -> SuaveTxType {
+        match self {
+            SuaveReceiptEnvelope::Legacy(_) => SuaveTxType::Legacy,
+            SuaveReceiptEnvelope::Eip2930(_) => SuaveTxType::Eip2930,
+            SuaveReceiptEnvelope::Eip1559(_) => SuaveTxType::Eip1559,
+            SuaveReceiptEnvelope::SuaveTransaction(_) => SuaveTxType::SuaveTransaction,
+        }
+    }
+
+    pub fn as_receipt_with_bloom(&self) -> &ReceiptWithBloom<T> {
+        match self {
+            SuaveReceiptEnvelope::Legacy(receipt)
+            | SuaveReceiptEnvelope::Eip2930(receipt)
+            | SuaveReceiptEnvelope::Eip1559(receipt)
+            | SuaveReceiptEnvelope::SuaveTransaction(receipt) => receipt,
+        }
+    }
+
+    pub fn status(&self) -> bool {
+        self.as_receipt_with_bloom().status()
+    }
+
+    pub fn cumulative_gas_used(&self) -> u128 {
+        self.as_receipt_with_bloom().cumulative_gas_used()
+    }
+
+    pub fn logs(&self) -> &[T] {
+        self.as_receipt_with_bloom().logs()
+    }
+
+    pub fn logs_bloom(&self) -> Bloom {
+        self.as_receipt_with_bloom().bloom()
+    }
+
+}
+
+impl Encodable2718 for SuaveReceiptEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            SuaveReceiptEnvelope::Legacy(_) => None,
+            _ => Some(self.tx_type().into()),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.as_receipt_with_bloom().length() + !self.is_legacy() as usize
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        if let Some(ty) = self.type_flag() {
+            out.put_u8(ty);
+        }
+        self.as_receipt_with_bloom().encode(out);
+    }
+}
+
+impl Decodable2718 for SuaveReceiptEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let tx_type = SuaveTxType::try_from(ty)
+            .map_err(|_| SuaveTypesError::UnexpectedTxType(ty))?;
+        match tx_type {
+            SuaveTxType::Eip2930 => Ok(SuaveReceiptEnvelope::Eip2930(Decodable::decode(buf)?)),
+            SuaveTxType::Eip1559 => Ok(SuaveReceiptEnvelope::Eip1559(Decodable::decode(buf)?)),
+            SuaveTxType::SuaveTransaction => Ok(SuaveReceiptEnvelope::SuaveTransaction(Decodable::decode(buf)?)),
+            // Confidential compute records and requests are not mined, only their SuaveTransaction
+            _ => Err(SuaveTypesError::UnexpectedTxType(ty).into()),
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(SuaveReceiptEnvelope::Legacy(Decodable::decode(buf)?))
+    }
+}
+
+impl<T> TryFrom<ReceiptEnvelope<T>> for SuaveReceiptEnvelope<T> {
+    type Error = SuaveTypesError;
+
+    fn try_from(envelope: ReceiptEnvelope<T>) -> Result<Self> {
+        match envelope {
+            ReceiptEnvelope::Legacy(receipt) => Ok(SuaveReceiptEnvelope::Legacy(receipt)),
+            ReceiptEnvelope::Eip2930(receipt) => Ok(SuaveReceiptEnvelope::Eip2930(receipt)),
+            ReceiptEnvelope::Eip1559(receipt) => Ok(SuaveReceiptEnvelope::Eip1559(receipt)),
+            other => Err(SuaveTypesError::UnexpectedTxType(other.tx_type() as u8)),
+        }
+    }
+}
+
+/// Transaction receipt returned by SUAVE nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuaveTransactionReceipt {
+    #[serde(flatten)]
+    pub receipt: TransactionReceipt<SuaveReceiptEnvelope<Log>>,
+    /// Hash of the 0x42-encoded request record the kettle executed, for SuaveTransaction receipts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_record_hash: Option<B256>,
+}
+
+impl SuaveTransactionReceipt {
+
+    pub fn tx_type(&self) -> SuaveTxType {
+        self.receipt.inner.tx_type()
+    }
+
+    pub fn is_suave_transaction(&self) -> bool {
+        self.tx_type() == SuaveTxType::SuaveTransaction
+    }
+
+    pub fn status(&self) -> bool {
+        self.receipt.inner.status()
+    }
+
+    pub fn gas_used(&self) -> u128 {
+        self.receipt.gas_used
+    }
+
+    pub fn logs(&self) -> &[Log] {
+        self.receipt.inner.logs()
+    }
+
+    pub fn logs_bloom(&self) -> Bloom {
+        self.receipt.inner.logs_bloom()
+    }
+
+    pub fn transaction_hash(&self) -> B256 {
+        self.receipt.transaction_hash
+    }
+
+    /// Links the receipt to the request record of `response`, the SuaveTransaction it belongs to.
+    pub fn link_response(&mut self, response: &ConfidentialCallResponse) -> Result<()> {
+        if !self.is_suave_transaction() {
+            return Err(SuaveTypesError::UnexpectedTxType(self.tx_type().into()));
+        }
+        if response.transaction.hash != self.receipt.transaction_hash {
+            return Err(SuaveTypesError::MalformedResponse(format!(
+                "receipt is for transaction {}, response is for {}",
+                self.receipt.transaction_hash, response.transaction.hash
+            )));
+        }
+        self.request_record_hash = Some(response.request_hash()?.into_b256());
+        Ok(())
+    }
+
+}
+
+#[cfg(feature = "network")]
+impl ReceiptResponse for SuaveTransactionReceipt {
+    fn contract_address(&self) -> Option<Address> {
+        self.receipt.contract_address
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, b256, bytes, LogData};
+    use alloy::rpc::types::eth::Transaction;
+    use super::*;
+    use crate::test_utils::{response, REQUEST_HASH, RESPONSE};
+    use super::super::SuaveTransaction;
+    use proptest::prelude::*;
+    use crate::test_utils::{arb_address, arb_bytes, arb_u128};
+
+    // Not captured from a node: `RESPONSE` was captured before its 0x50 transaction was mined,
+    // so this is built around it, with a made-up block and log. The bloom is the one of its log.
+    const RECEIPT: &str = r#"{"blockHash":"0x4c1f5e0b4d0a4f3cb3fbd0a0dc3b4fb4fd2c0f1a1de5d2ba6b1c2e1f1e0d9c8b","blockNumber":"0x1a2b","contractAddress":null,"cumulativeGasUsed":"0x1e8e4","effectiveGasPrice":"0x8c9aca00","from":"0x03493869959c866713c33669ca118e774a30a0e5","gasUsed":"0x1e8e4","logs":[{"address":"0xc803334c79650708daf3a3462ac4b48296b1352a","topics":["0x000000000000000000000000000000000000000000000000000000000000dead"],"data":"0x0000000000000000000000000000000000000000000000000000000001ccb310","blockNumber":"0x1a2b","transactionHash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","transactionIndex":"0x0","blockHash":"0x4c1f5e0b4d0a4f3cb3fbd0a0dc3b4fb4fd2c0f1a1de5d2ba6b1c2e1f1e0d9c8b","logIndex":"0x0","removed":false}],"logsBloom":"0x00000000000010000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionHash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","transactionIndex":"0x0","type":"0x50"}"#;
+
+    fn receipt() -> SuaveTransactionReceipt {
+        serde_json::from_str(RECEIPT).unwrap()
+    }
+
+    #[test]
+    fn test_parse_suave_tx_receipt() {
+        let receipt = receipt();
+
+        assert!(receipt.is_suave_transaction());
+        assert!(receipt.status());
+        assert_eq!(receipt.gas_used(), 0x1e8e4);
+        assert_eq!(receipt.receipt.inner.cumulative_gas_used(), 0x1e8e4);
+        assert_eq!(receipt.logs().len(), 1);
+        assert_eq!(receipt.logs()[0].address(), address!("c803334c79650708daf3a3462ac4b48296b1352a"));
+        assert_eq!(receipt.logs_bloom(), receipt.logs().iter().map(|log| &log.inner).collect::<Bloom>());
+        assert_ne!(receipt.logs_bloom(), Bloom::ZERO);
+        assert_eq!(receipt.request_record_hash, None);
+        assert_eq!(receipt.receipt.contract_address, None);
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["type"], "0x50");
+        assert_eq!(serde_json::from_value::<SuaveTransactionReceipt>(json).unwrap(), receipt);
+    }
+
+    #[test]
+    fn test_link_receipt_to_response() {
+        let response_tx: Transaction = serde_json::from_str(RESPONSE).unwrap();
+        let response = ConfidentialCallResponse::try_from(response_tx).unwrap();
+        let mut receipt = receipt();
+
+        receipt.link_response(&response).unwrap();
+        assert_eq!(receipt.request_record_hash, Some(REQUEST_HASH));
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(serde_json::from_value::<SuaveTransactionReceipt>(json).unwrap().request_record_hash, Some(REQUEST_HASH));
+
+        receipt.receipt.transaction_hash = B256::ZERO;
+        assert!(matches!(receipt.link_response(&response), Err(SuaveTypesError::MalformedResponse(_))));
+    }
+
+    // `get_transaction_receipt` with the request's hash, answered with the receipt of the
+    // SuaveTransaction that executed it, or `null` while it is pending. The node doesn't write
+    // `requestRecordHash`; `SuaveProvider::get_request_receipt` links it from the transaction.
+    #[test]
+    fn test_receipt_by_request_hash() {
+        let params = serde_json::json!([REQUEST_HASH]);
+        assert_eq!(params[0], "0x3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9");
+
+        let result: serde_json::Value = serde_json::from_str(RECEIPT).unwrap();
+        let receipt: Option<SuaveTransactionReceipt> = serde_json::from_value(result).unwrap();
+        let mut receipt = receipt.unwrap();
+        assert!(receipt.is_suave_transaction());
+        assert_eq!(receipt.request_record_hash, None);
+        assert_eq!(receipt.transaction_hash(), response().transaction.hash);
+
+        receipt.link_response(&response()).unwrap();
+        assert_eq!(receipt.request_record_hash, Some(REQUEST_HASH));
+
+        let pending: Option<SuaveTransactionReceipt> = serde_json::from_value(serde_json::Value::Null).unwrap();
+        assert_eq!(pending, None);
+    }
+
+    #[test]
+    fn test_receipt_envelope_2718() {
+        let log = PrimitiveLog {
+            address: address!("c803334c79650708daf3a3462ac4b48296b1352a"),
+            data: LogData::new_unchecked(
+                vec![b256!("000000000000000000000000000000000000000000000000000000000000dead")],
+                bytes!("01ccb310"),
+            ),
+        };
+        let receipt: ReceiptWithBloom = serde_json::from_value(serde_json::json!({
+            "status": "0x1",
+            "cumulativeGasUsed": "0x1e8e4",
+            "logs": [log],
+            "logsBloom": Bloom::ZERO,
+        })).unwrap();
+
+        for envelope in [
+            SuaveReceiptEnvelope::Legacy(receipt.clone()),
+            SuaveReceiptEnvelope::Eip1559(receipt.clone()),
+            SuaveReceiptEnvelope::SuaveTransaction(receipt.clone()),
+        ] {
+            let mut encoded = Vec::new();
+            envelope.encode_2718(&mut encoded);
+            assert_eq!(encoded.len(), envelope.encode_2718_len());
+            assert_eq!(encoded.first() == Some(&SuaveTransaction::TYPE), envelope.tx_type() == SuaveTxType::SuaveTransaction);
+
+            let decoded = SuaveReceiptEnvelope::decode_2718(&mut encoded.as_slice()).unwrap();
+            assert_eq!(decoded, envelope);
+            assert!(decoded.status());
+            assert_eq!(decoded.logs(), std::slice::from_ref(&log));
+        }
+
+        let err = SuaveReceiptEnvelope::decode_2718(&mut [0x43_u8, 0xc0].as_slice()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("unexpected tx type"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_receipt_encoded_lengths(
+            status in any::<bool>(),
+            cumulative_gas_used in arb_u128(),
+            logs in prop::collection::vec((arb_address(), prop::collection::vec(any::<[u8; 32]>(), 0..=4), arb_bytes()), 0..4),
+        ) {
+            let logs: Vec<_> = logs.into_iter().map(|(address, topics, data)| PrimitiveLog {
+                address,
+                data: LogData::new_unchecked(topics.into_iter().map(B256::from).collect(), data),
+            }).collect();
+            let receipt: ReceiptWithBloom = serde_json::from_value(serde_json::json!({
+                "status": if status { "0x1" } else { "0x0" },
+                "cumulativeGasUsed": format!("{cumulative_gas_used:#x}"),
+                "logs": logs,
+                "logsBloom": Bloom::ZERO,
+            })).unwrap();
+
+            for envelope in [
+                SuaveReceiptEnvelope::Legacy(receipt.clone()),
+                SuaveReceiptEnvelope::Eip1559(receipt.clone()),
+                SuaveReceiptEnvelope::SuaveTransaction(receipt),
+            ] {
+                let mut encoded = Vec::new();
+                envelope.encode_2718(&mut encoded);
+                prop_assert_eq!(envelope.encode_2718_len(), encoded.len());
+            }
+        }
+    }
+
+}
@@ -0,0 +1,247 @@
+use alloy_primitives::ChainId;
+#[cfg(feature = "rlp")]
+use alloy_primitives::{keccak256, Bytes, Signature};
+#[cfg(feature = "consensus")]
+use alloy_rlp::Encodable;
+#[cfg(feature = "rlp")]
+use alloy_sol_types::SolStruct;
+#[cfg(feature = "rlp")]
+use super::crecord::{signature_to_vrs, CRecordRLP, ConfidentialComputeRecord};
+use super::crequest::CRequestHashParams;
+#[cfg(feature = "rlp")]
+use super::crequest::encode_with_prefix;
+use super::eip712::ConfidentialRecord;
+#[cfg(feature = "rlp")]
+use super::eip712::eip712_domain;
+#[cfg(feature = "consensus")]
+use super::eip712::{eip712_signing_payload, EIP712_SIGNING_PAYLOAD_LEN};
+use super::error::{Result, SuaveTypesError};
+#[cfg(feature = "rlp")]
+use super::hash::{RecordSigningHash, RequestHash};
+
+
+/// Encoding and hashing rules of a SUAVE network, selected from the chain id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuaveSpec {
+    pub name: &'static str,
+    /// Records carry the `is_eip712` flag in their RLP and may be signed as EIP-712 typed data.
+    pub eip712_records: bool,
+}
+
+impl SuaveSpec {
+    pub const RIGIL_CHAIN_ID: ChainId = 0x1008c45;
+    pub const TOLIMAN_CHAIN_ID: ChainId = 0x201118a;
+
+    /// Rigil testnet, which predates EIP-712 records.
+    pub const RIGIL: Self = Self {
+        name: "rigil",
+        eip712_records: false,
+    };
+
+    pub const TOLIMAN: Self = Self {
+        name: "toliman",
+        eip712_records: true,
+    };
+
+    /// Rules used for chains without a preset, such as local devnets.
+    pub const LATEST: Self = Self::TOLIMAN;
+
+    pub const fn from_chain_id(chain_id: ChainId) -> Self {
+        match chain_id {
+            Self::RIGIL_CHAIN_ID => Self::RIGIL,
+            Self::TOLIMAN_CHAIN_ID => Self::TOLIMAN,
+            _ => Self::LATEST,
+        }
+    }
+
+    /// Fails if a record asks for EIP-712 signing on a network without EIP-712 records.
+    pub(crate) fn check_eip712(&self, is_eip712: bool) -> Result<()> {
+        if is_eip712 && !self.eip712_records {
+            return Err(SuaveTypesError::InvalidField("is_eip712"));
+        }
+        Ok(())
+    }
+
+    /// EIP-712 struct of a record, on networks that have EIP-712 records.
+    pub(crate) fn eip712_record(&self, params: &CRequestHashParams<'_>) -> Result<ConfidentialRecord> {
+        self.check_eip712(true)?;
+        Ok(ConfidentialRecord::from(params))
+    }
+
+    /// Hash the sender signs, the EIP-712 hash or the hash of the 0x42-prefixed signing RLP.
+    /// Fails if the record asks for EIP-712 signing on a network without EIP-712 records.
+    #[cfg(feature = "rlp")]
+    pub(crate) fn record_signing_hash(&self, params: &CRequestHashParams<'_>, is_eip712: bool) -> Result<RecordSigningHash> {
+        self.check_eip712(is_eip712)?;
+        Ok(record_signing_hash_unchecked(params, is_eip712))
+    }
+
+    /// Writes the payload whose keccak is [`record_signing_hash`](Self::record_signing_hash).
+    /// Can't fail, so it follows the flag whatever the network; the flag is then kept in the
+    /// record RLP, where decoding rejects it.
+    #[cfg(feature = "consensus")]
+    pub(crate) fn encode_record_for_signing(
+        &self,
+        params: &CRequestHashParams<'_>,
+        is_eip712: bool,
+        out: &mut dyn alloy_rlp::BufMut,
+    ) {
+        if is_eip712 {
+            let domain = eip712_domain(params.kettle_address);
+            out.put_slice(&eip712_signing_payload(&domain, &ConfidentialRecord::from(params)));
+            return;
+        }
+        out.put_u8(ConfidentialComputeRecord::TYPE);
+        params.encode(out);
+    }
+
+    #[cfg(feature = "consensus")]
+    pub(crate) fn record_signing_payload_len(&self, params: &CRequestHashParams<'_>, is_eip712: bool) -> usize {
+        if is_eip712 {
+            return EIP712_SIGNING_PAYLOAD_LEN;
+        }
+        params.length() + 1
+    }
+
+    /// RLP of a signed record, with the `is_eip712` field on networks whose records carry it and
+    /// whenever it is set, so an unsupported flag is rejected on decoding rather than dropped.
+    #[cfg(feature = "rlp")]
+    pub(crate) fn record_rlp<'a>(
+        &self,
+        params: CRequestHashParams<'a>,
+        is_eip712: bool,
+        chain_id: ChainId,
+        signature: Signature,
+    ) -> CRecordRLP<&'a Bytes> {
+        let (v, r, s) = signature_to_vrs(signature);
+        CRecordRLP {
+            nonce: params.nonce,
+            gas_price: params.gas_price,
+            gas: params.gas.into(),
+            to: params.to,
+            value: params.value,
+            input: params.input,
+            kettle_address: params.kettle_address,
+            confidential_inputs_hash: params.confidential_inputs_hash,
+            is_eip712: (is_eip712 || self.eip712_records).then_some(is_eip712),
+            chain_id,
+            v, r, s,
+        }
+    }
+
+    /// Transaction hash of a signed record, the hash of its 0x42-prefixed RLP.
+    #[cfg(feature = "rlp")]
+    pub(crate) fn request_hash(
+        &self,
+        params: CRequestHashParams<'_>,
+        is_eip712: bool,
+        chain_id: ChainId,
+        signature: Signature,
+    ) -> RequestHash {
+        let record_rlp = self.record_rlp(params, is_eip712, chain_id, signature);
+        keccak256(encode_with_prefix(ConfidentialComputeRecord::TYPE, record_rlp)).into()
+    }
+
+}
+
+impl Default for SuaveSpec {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+impl From<ChainId> for SuaveSpec {
+    fn from(chain_id: ChainId) -> Self {
+        Self::from_chain_id(chain_id)
+    }
+}
+
+/// Signing hash following the `is_eip712` flag, for records already checked against their network.
+#[cfg(feature = "rlp")]
+pub(crate) fn record_signing_hash_unchecked(params: &CRequestHashParams<'_>, is_eip712: bool) -> RecordSigningHash {
+    if is_eip712 {
+        let domain = eip712_domain(params.kettle_address);
+        return ConfidentialRecord::from(params).eip712_signing_hash(&domain).into();
+    }
+    keccak256(encode_with_prefix(ConfidentialComputeRecord::TYPE, params)).into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_from_chain_id() {
+        assert_eq!(SuaveSpec::from_chain_id(0x1008c45), SuaveSpec::RIGIL);
+        assert_eq!(SuaveSpec::from_chain_id(0x201118a), SuaveSpec::TOLIMAN);
+        assert_eq!(SuaveSpec::from_chain_id(0x067932), SuaveSpec::LATEST);
+        assert_ne!(SuaveSpec::RIGIL.eip712_records, SuaveSpec::LATEST.eip712_records);
+    }
+
+    #[cfg(feature = "rpc")]
+    #[test]
+    fn test_rigil_record_hashes() -> Result<()> {
+        use std::str::FromStr;
+        use alloy_primitives::B256;
+        use crate::test_utils::response;
+
+        let record = response().request_record;
+        let spec = SuaveSpec::RIGIL;
+        let chain_id = record.chain_id.unwrap();
+        let signature = record.signature.unwrap();
+        let params = CRequestHashParams::try_from(&record)?;
+
+        let rlp_hash = RecordSigningHash::from(keccak256(encode_with_prefix(ConfidentialComputeRecord::TYPE, &params)));
+        assert_eq!(spec.record_signing_hash(&params, false)?, rlp_hash);
+        let err = spec.record_signing_hash(&params, true).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::InvalidField("is_eip712")));
+
+        let mut payload = Vec::new();
+        spec.encode_record_for_signing(&params, false, &mut payload);
+        assert_eq!(payload.len(), spec.record_signing_payload_len(&params, false));
+        assert_eq!(RecordSigningHash::from(keccak256(&payload)), rlp_hash);
+
+        let record_rlp = spec.record_rlp(CRequestHashParams::try_from(&record)?, false, chain_id, signature);
+        assert_eq!(record_rlp.is_eip712, None);
+        let record_rlp = spec.record_rlp(CRequestHashParams::try_from(&record)?, true, chain_id, signature);
+        assert_eq!(record_rlp.is_eip712, Some(true));
+        let expected = B256::from_str("0x3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9").unwrap();
+        assert_eq!(spec.request_hash(params, false, chain_id, signature), RequestHash::from(expected));
+        assert!(spec.check_eip712(true).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "rpc")]
+    #[test]
+    fn test_toliman_record_hashes() -> Result<()> {
+        use crate::test_utils::devnet_record;
+
+        let record = devnet_record();
+        let spec = SuaveSpec::TOLIMAN;
+        let chain_id = record.chain_id.unwrap();
+        let signature = record.signature.unwrap();
+        let params = CRequestHashParams::try_from(&record)?;
+
+        let rlp_hash = RecordSigningHash::from(keccak256(encode_with_prefix(ConfidentialComputeRecord::TYPE, &params)));
+        assert_eq!(spec.record_signing_hash(&params, false)?, rlp_hash);
+        let eip712_hash = spec.record_signing_hash(&params, true)?;
+        assert_ne!(eip712_hash, rlp_hash);
+        let eip712_record = ConfidentialComputeRecord { is_eip712: true, ..record.clone() };
+        assert_eq!(eip712_hash.into_b256(), eip712_record.eip712_signing_hash()?);
+
+        let mut payload = Vec::new();
+        spec.encode_record_for_signing(&params, true, &mut payload);
+        assert_eq!(payload.len(), spec.record_signing_payload_len(&params, true));
+        assert_eq!(RecordSigningHash::from(keccak256(&payload)), eip712_hash);
+
+        let record_rlp = spec.record_rlp(CRequestHashParams::try_from(&record)?, true, chain_id, signature);
+        assert_eq!(record_rlp.is_eip712, Some(true));
+        let toliman_hash = spec.request_hash(CRequestHashParams::try_from(&record)?, false, chain_id, signature);
+        let rigil_hash = SuaveSpec::RIGIL.request_hash(params, false, chain_id, signature);
+        assert_ne!(toliman_hash, rigil_hash);
+        assert!(spec.check_eip712(true).is_ok());
+        Ok(())
+    }
+
+}
@@ -0,0 +1,333 @@
+use alloy_primitives::{self as primitives, Address, Bytes, ChainId, Signature, B256, U256};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+#[cfg(feature = "consensus")]
+use alloy_rlp::{Decodable, Encodable};
+#[cfg(feature = "consensus")]
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+#[cfg(feature = "rpc")]
+use alloy_rpc_types::eth::{Signature as RpcSignature, Transaction as RpcTransaction};
+use super::crecord::{signature_to_vrs, CRecordRLP, ConfidentialComputeRecord, SignedConfidentialComputeRecord};
+use super::crequest::encode_with_prefix;
+#[cfg(feature = "rpc")]
+use super::cresponse::ConfidentialCallResponse;
+use super::error::Result;
+#[cfg(feature = "k256")]
+use super::error::SuaveTypesError;
+use super::hash::SuaveTransactionHash;
+use super::spec::record_signing_hash_unchecked;
+
+
+/// Kettle-signed transaction carrying the request record and the confidential compute result.
+/// Serializes to the 0x50 transaction JSON of suave-geth.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuaveTransaction {
+    request_record: SignedConfidentialComputeRecord,
+    confidential_compute_result: Bytes,
+    chain_id: ChainId,
+    signature: Signature,
+}
+
+impl SuaveTransaction {
+    pub const TYPE: u8 = 0x50;
+
+    /// Fails if the request record isn't complete and signed.
+    pub fn new(
+        request_record: ConfidentialComputeRecord,
+        confidential_compute_result: Bytes,
+        chain_id: ChainId,
+        signature: Signature,
+    ) -> Result<Self> {
+        Ok(Self {
+            request_record: SignedConfidentialComputeRecord::try_from(request_record)?,
+            confidential_compute_result,
+            chain_id,
+            signature,
+        })
+    }
+
+    pub fn request_record(&self) -> &SignedConfidentialComputeRecord {
+        &self.request_record
+    }
+
+    pub fn confidential_compute_result(&self) -> &Bytes {
+        &self.confidential_compute_result
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn with_signature(mut self, signature: Signature) -> Self {
+        self.set_signature(signature);
+        self
+    }
+
+    pub fn set_signature(&mut self, signature: Signature) {
+        self.signature = signature;
+    }
+
+    pub fn rlp_encode(&self) -> Bytes {
+        encode_with_prefix(SuaveTransaction::TYPE, SuaveTxRLP::from(self))
+    }
+
+    /// Hash of the 0x50-encoded transaction.
+    pub fn hash(&self) -> SuaveTransactionHash {
+        primitives::keccak256(self.rlp_encode()).into()
+    }
+
+    /// Hash signed by the kettle.
+    pub fn signature_hash(&self) -> B256 {
+        let record = self.request_record.tx();
+        let hash_params = SuaveTxHashParams {
+            // `new` checked the record against its network.
+            request_record_hash: record_signing_hash_unchecked(&record.hash_params(), record.is_eip712()).into(),
+            confidential_compute_result: &self.confidential_compute_result,
+        };
+        primitives::keccak256(encode_with_prefix(SuaveTransaction::TYPE, hash_params))
+    }
+
+    /// Recovers the kettle address from the transaction signature.
+    #[cfg(feature = "k256")]
+    pub fn recover_signer(&self) -> Result<Address> {
+        Ok(self.signature.recover_address_from_prehash(&self.signature_hash())?)
+    }
+
+    pub fn kettle_address(&self) -> Address {
+        self.request_record.tx().kettle_address()
+    }
+
+}
+
+#[cfg(feature = "consensus")]
+impl Encodable2718 for SuaveTransaction {
+    fn type_flag(&self) -> Option<u8> {
+        Some(SuaveTransaction::TYPE)
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        SuaveTxRLP::from(self).length() + 1
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        out.put_u8(SuaveTransaction::TYPE);
+        SuaveTxRLP::from(self).encode(out);
+    }
+}
+
+#[cfg(feature = "consensus")]
+impl Decodable2718 for SuaveTransaction {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match ty {
+            SuaveTransaction::TYPE => {
+                let suave_tx_rlp = SuaveTxRLP::decode(buf)?;
+                Ok(SuaveTransaction::try_from(suave_tx_rlp)?)
+            }
+            _ => Err(SuaveTypesError::UnexpectedTxType(ty).into())
+        }
+    }
+
+    fn fallback_decode(_buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Err(alloy_rlp::Error::Custom("Only SuaveTransaction"))
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl TryFrom<&ConfidentialCallResponse> for SuaveTransaction {
+    type Error = SuaveTypesError;
+
+    fn try_from(response: &ConfidentialCallResponse) -> Result<Self> {
+        let tx = &response.transaction;
+        let sig = tx.signature.ok_or(SuaveTypesError::MissingField("signature"))?;
+        let v = u64::try_from(sig.v)
+            .map_err(|_| SuaveTypesError::MalformedResponse(format!("invalid v: {}", sig.v)))?;
+        let signature = Signature::from_rs_and_parity(sig.r, sig.s, v)?;
+        Self::new(
+            response.request_record.clone(),
+            response.confidential_compute_result.clone(),
+            tx.chain_id.ok_or(SuaveTypesError::MissingField("chain_id"))?,
+            signature,
+        )
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl TryFrom<SuaveTransaction> for ConfidentialCallResponse {
+    type Error = SuaveTypesError;
+
+    fn try_from(suave_tx: SuaveTransaction) -> Result<Self> {
+        let from = suave_tx.request_record.recover_signer()?;
+        let hash = suave_tx.hash().into();
+        let (v, r, s) = signature_to_vrs(suave_tx.signature);
+        let request_record = ConfidentialComputeRecord { from: Some(from), ..suave_tx.request_record.into_record() };
+        let transaction = RpcTransaction {
+            hash,
+            nonce: request_record.nonce.ok_or(SuaveTypesError::MissingField("nonce"))?,
+            from,
+            to: Some(request_record.to),
+            value: request_record.value,
+            gas_price: request_record.gas_price,
+            gas: request_record.gas.ok_or(SuaveTypesError::MissingField("gas"))?,
+            input: suave_tx.confidential_compute_result.clone(),
+            signature: Some(RpcSignature { r, s, v: U256::from(v), ..Default::default() }),
+            chain_id: Some(suave_tx.chain_id),
+            transaction_type: Some(SuaveTransaction::TYPE),
+            ..Default::default()
+        };
+
+        Ok(Self {
+            transaction,
+            confidential_compute_result: suave_tx.confidential_compute_result,
+            request_record,
+        })
+    }
+}
+
+#[derive(Debug, RlpEncodable, RlpDecodable, PartialEq)]
+struct SuaveTxRLP<B = Bytes> {
+    request_record: CRecordRLP<B>,
+    confidential_compute_result: B,
+    chain_id: u64,
+    v: u8,
+    r: U256,
+    s: U256,
+}
+
+impl<'a> From<&'a SuaveTransaction> for SuaveTxRLP<&'a Bytes> {
+    fn from(suave_tx: &'a SuaveTransaction) -> Self {
+        let (v, r, s) = signature_to_vrs(suave_tx.signature);
+        let request_record = &suave_tx.request_record;
+        Self {
+            request_record: request_record.tx().record_rlp(*request_record.signature()),
+            confidential_compute_result: &suave_tx.confidential_compute_result,
+            chain_id: suave_tx.chain_id,
+            v, r, s
+        }
+    }
+}
+
+#[cfg(feature = "k256")]
+impl TryFrom<SuaveTxRLP> for SuaveTransaction {
+    type Error = SuaveTypesError;
+
+    fn try_from(rlp: SuaveTxRLP) -> Result<Self> {
+        let request_record = ConfidentialComputeRecord::try_from(rlp.request_record)?;
+        let signature = Signature::from_rs_and_parity(rlp.r, rlp.s, rlp.v as u64)?;
+        Self::new(request_record, rlp.confidential_compute_result, rlp.chain_id, signature)
+    }
+}
+
+#[derive(Debug, RlpEncodable, PartialEq)]
+struct SuaveTxHashParams<'a> {
+    request_record_hash: B256,
+    confidential_compute_result: &'a Bytes,
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use std::str::FromStr;
+    use alloy::signers::{wallet::LocalWallet, Signer};
+    use eyre::Result;
+    use super::*;
+    use proptest::prelude::*;
+    use crate::test_utils::{arb_bytes, arb_signature, arb_signed_record, arb_u64, devnet_record, response, rigil_kettle};
+
+    #[test]
+    fn test_suave_tx_recover_kettle() -> Result<()> {
+        let suave_tx = SuaveTransaction::try_from(&response())?;
+
+        let expected_hash = B256::from_str("0xb45aab00a0e94d8e9c46712dfd67cbc66e685f93e238a8f82047e88e0078fd42")?;
+        assert_eq!(suave_tx.signature_hash(), expected_hash);
+        assert_eq!(suave_tx.recover_signer()?, rigil_kettle());
+        assert_eq!(suave_tx.kettle_address(), suave_tx.recover_signer()?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_suave_tx_encode_decode() -> Result<()> {
+        let kettle: LocalWallet = "0x2222222222222222222222222222222222222222222222222222222222222222".parse()?;
+        let mut suave_tx = SuaveTransaction::try_from(&response())?;
+        suave_tx.set_signature(kettle.sign_hash(&suave_tx.signature_hash()).await?);
+
+        let encoded = suave_tx.rlp_encode();
+        assert_eq!(suave_tx.encode_2718_len(), encoded.len());
+        let mut encoded_2718 = Vec::new();
+        suave_tx.encode_2718(&mut encoded_2718);
+        assert_eq!(encoded_2718, encoded.to_vec());
+
+        let decoded = SuaveTransaction::decode_2718(&mut encoded.as_ref())?;
+        assert_eq!(decoded, suave_tx);
+        assert_eq!(decoded.hash().into_b256(), primitives::keccak256(&encoded));
+        assert_eq!(decoded.recover_signer()?, kettle.address());
+
+        let mut encoded = encoded.to_vec();
+        encoded[0] = ConfidentialComputeRecord::TYPE;
+        let err = SuaveTransaction::decode_2718(&mut encoded.as_slice()).unwrap_err();
+        assert_eq!(err, alloy_rlp::Error::Custom("unexpected tx type"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suave_tx_to_response() -> Result<()> {
+        let expected = response();
+        let suave_tx = SuaveTransaction::try_from(&expected)?;
+        let response = ConfidentialCallResponse::try_from(suave_tx.clone())?;
+
+        assert_eq!(response.request_record, expected.request_record);
+        assert_eq!(response.confidential_compute_result, expected.confidential_compute_result);
+        assert_eq!(response.hash(), suave_tx.hash());
+        assert_eq!(response.transaction.from, expected.transaction.from);
+        assert_eq!(response.transaction.to, expected.transaction.to);
+        assert_eq!(response.transaction.nonce, expected.transaction.nonce);
+        assert_eq!(response.transaction.gas, expected.transaction.gas);
+        assert_eq!(response.transaction.gas_price, expected.transaction.gas_price);
+        assert_eq!(response.transaction.input, expected.transaction.input);
+        assert_eq!(response.transaction.chain_id, expected.transaction.chain_id);
+        assert_eq!(response.transaction.transaction_type, expected.transaction.transaction_type);
+        assert_eq!(SuaveTransaction::try_from(&response)?, suave_tx);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suave_tx_from_response_invalid_v() {
+        let mut response = response();
+        response.transaction.signature.as_mut().unwrap().v = U256::MAX;
+        let err = SuaveTransaction::try_from(&response).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MalformedResponse(msg) if msg.starts_with("invalid v")));
+    }
+
+    #[test]
+    fn test_suave_tx_incomplete_record() {
+        let signature = devnet_record().signature.unwrap();
+        let record = ConfidentialComputeRecord { nonce: None, ..devnet_record() };
+        let err = SuaveTransaction::new(record, Bytes::new(), 0x067932, signature).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("nonce")));
+
+        let record = ConfidentialComputeRecord { signature: None, ..devnet_record() };
+        let err = SuaveTransaction::new(record, Bytes::new(), 0x067932, signature).unwrap_err();
+        assert!(matches!(err, SuaveTypesError::MissingField("signature")));
+    }
+
+    proptest! {
+        #[test]
+        fn test_encoded_lengths(
+            record in arb_signed_record(),
+            result in arb_bytes(),
+            chain_id in arb_u64(),
+            signature in arb_signature(),
+        ) {
+            let suave_tx = SuaveTransaction::new(record, result, chain_id, signature).unwrap();
+            let mut encoded = Vec::new();
+            suave_tx.encode_2718(&mut encoded);
+            prop_assert_eq!(suave_tx.encode_2718_len(), encoded.len());
+        }
+    }
+
+}
@@ -0,0 +1,95 @@
+//! Fixtures and proptest strategies shared by the tests.
+
+use std::str::FromStr;
+use proptest::prelude::*;
+use alloy::primitives::{b256, Address, Bytes, FixedBytes, Signature, B256, U256};
+use super::crecord::ConfidentialComputeRecord;
+use super::cresponse::ConfidentialCallResponse;
+pub(crate) use super::fuzz::{arb_address, arb_bytes, arb_u128, arb_u256, arb_u64};
+
+
+/// Returned by a Rigil kettle for `queryLatestPrice("ETHUSDT")`.
+pub(crate) const RESPONSE: &str = r#"{"blockHash":null,"blockNumber":null,"chainId":"0x1008c45","confidentialComputeResult":"0x0000000000000000000000000000000000000000000000000000000001ccb310","from":"0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2","input":"0x0000000000000000000000000000000000000000000000000000000001ccb310","nonce":"0x45","r":"0x85242d1876ce1d6a655fd485346628f3df18a051be0f8efa4bfa40b9e85a3dfe","requestRecord":{"chainId":"0x1008c45","confidentialInputsHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","gas":"0xf4240","gasPrice":"0x8c9aca00","hash":"0x3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9","input":"0x50723553000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000074554485553445400","kettleAddress":"0x03493869959c866713c33669ca118e774a30a0e5","maxFeePerGas":null,"maxPriorityFeePerGas":null,"nonce":"0x45","r":"0xc1c5071f78c6f6b6380ebc4957dd4f6c74bdf5be742ad0d62d2d75f510e33660","s":"0x5de5c97f9c5ee5c5dad3bb0d591e581f48cd947e998d32500bb73de24dd7a6f9","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","type":"0x42","v":"0x0","value":"0x0"},"s":"0x4f0880f42d42b1de17f97c33749d60a46bd1f493c6547f08ac2bed0c6d111861","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionIndex":null,"type":"0x50","v":"0x1","value":"0x0"}"#;
+
+/// `requestRecord.hash` of [`RESPONSE`], as the node reported it.
+pub(crate) const REQUEST_HASH: B256 = b256!("3d753c496bb9053c7da2cdbbe170614d3e9408ee12ba521c72c2b21e151b7ab9");
+
+pub(crate) fn response() -> ConfidentialCallResponse {
+    serde_json::from_str(RESPONSE).unwrap()
+}
+
+/// Address of the 0x11.. key, which signed the Rigil request and the devnet record.
+pub(crate) fn sender() -> Address {
+    Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a").unwrap()
+}
+
+pub(crate) fn rigil_kettle() -> Address {
+    Address::from_str("0x03493869959c866713c33669ca118e774a30a0e5").unwrap()
+}
+
+pub(crate) fn devnet_kettle() -> Address {
+    Address::from_str("0x7d83e42b214b75bf1f3e57adc3415da573d97bff").unwrap()
+}
+
+/// Devnet record, signed with the 0x11.. key.
+pub(crate) fn devnet_record() -> ConfidentialComputeRecord {
+    let r = U256::from_str("0x1567c31c4bebcd1061edbaf22dd73fd40ff30f9a3ba4525037f23b2dc61e3473").unwrap();
+    let s = U256::from_str("0x2dce69262794a499d525c5d58edde33e06a5847b4d321d396b743700a2fd71a8").unwrap();
+    ConfidentialComputeRecord {
+        nonce: Some(0x22),
+        to: Address::from_str("0x780675d71ebe3d3ef05fae379063071147dd3aee").unwrap(),
+        gas: Some(0x0f4240),
+        gas_price: Some(0x3b9aca00),
+        input: Bytes::from_str("0x236eb5a70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001000000000000000000000000780675d71ebe3d3ef05fae379063071147dd3aee0000000000000000000000000000000000000000000000000000000000000000").unwrap(),
+        kettle_address: Some(devnet_kettle()),
+        chain_id: Some(0x067932),
+        confidential_inputs_hash: Some(B256::from_str("0x89ee438ca379ac86b0478517d43a6a9e078cf51543acac0facd68aff313e2ff1").unwrap()),
+        signature: Some(Signature::from_rs_and_parity(r, s, false).unwrap()),
+        ..ConfidentialComputeRecord::default()
+    }
+}
+
+
+// Scalars are kept below the curve order and non-zero, which is all encoding needs.
+pub(crate) fn arb_signature() -> impl Strategy<Value = Signature> {
+    (any::<[u8; 32]>(), any::<[u8; 32]>(), any::<bool>()).prop_map(|(r, s, parity)| {
+        let scalar = |mut bytes: [u8; 32]| {
+            bytes[0] &= 0x7f;
+            bytes[31] |= 1;
+            U256::from_be_bytes(bytes)
+        };
+        Signature::from_rs_and_parity(scalar(r), scalar(s), parity).unwrap()
+    })
+}
+
+/// A record with every encoded field set; gas fits in a `u64` so it can back a request.
+pub(crate) fn arb_signed_record() -> impl Strategy<Value = ConfidentialComputeRecord> {
+    (
+        arb_u64(),
+        arb_u64().prop_map(u128::from),
+        arb_u128(),
+        arb_address(),
+        arb_u256(),
+        arb_bytes(),
+        arb_address(),
+        arb_u64(),
+        any::<[u8; 32]>().prop_map(FixedBytes::from),
+        any::<bool>(),
+        arb_signature(),
+    ).prop_map(|(nonce, gas, gas_price, to, value, input, kettle_address, chain_id, cinputs_hash, is_eip712, signature)| {
+        ConfidentialComputeRecord {
+            nonce: Some(nonce),
+            to,
+            gas: Some(gas),
+            gas_price: Some(gas_price),
+            value,
+            input,
+            kettle_address: Some(kettle_address),
+            chain_id: Some(chain_id),
+            confidential_inputs_hash: Some(cinputs_hash),
+            is_eip712,
+            signature: Some(signature),
+            from: None,
+        }
+    })
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_rpc_types::eth::Transaction;
+use super::cresponse::{ConfidentialCallResponse, SuaveRpcTransaction};
+use super::crecord::ConfidentialComputeRecord;
+use super::envelope::SuaveTxType;
+use super::suavetx::SuaveTransaction;
+use super::error::SuaveTypesError;
+
+
+/// Transaction returned by SUAVE nodes, either an ordinary Ethereum transaction
+/// or a confidential call response for a [`SuaveTransaction`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum SuaveTransactionResponse {
+    Ethereum(Transaction),
+    ConfidentialCall(ConfidentialCallResponse),
+}
+
+impl SuaveTransactionResponse {
+
+    pub fn transaction(&self) -> &Transaction {
+        match self {
+            SuaveTransactionResponse::Ethereum(tx) => tx,
+            SuaveTransactionResponse::ConfidentialCall(response) => &response.transaction,
+        }
+    }
+
+    pub fn hash(&self) -> B256 {
+        self.transaction().hash
+    }
+
+    pub fn from(&self) -> Address {
+        self.transaction().from
+    }
+
+    /// Transaction type, `None` if the node reported a type unknown to SUAVE.
+    pub fn tx_type(&self) -> Option<SuaveTxType> {
+        let ty = self.transaction().transaction_type.unwrap_or_default();
+        SuaveTxType::try_from(ty).ok()
+    }
+
+    pub fn is_confidential_call(&self) -> bool {
+        matches!(self, SuaveTransactionResponse::ConfidentialCall(_))
+    }
+
+    pub fn as_ethereum(&self) -> Option<&Transaction> {
+        match self {
+            SuaveTransactionResponse::Ethereum(tx) => Some(tx),
+            SuaveTransactionResponse::ConfidentialCall(_) => None,
+        }
+    }
+
+    pub fn as_confidential_call(&self) -> Option<&ConfidentialCallResponse> {
+        match self {
+            SuaveTransactionResponse::Ethereum(_) => None,
+            SuaveTransactionResponse::ConfidentialCall(response) => Some(response),
+        }
+    }
+
+    pub fn into_confidential_call(self) -> Option<ConfidentialCallResponse> {
+        match self {
+            SuaveTransactionResponse::Ethereum(_) => None,
+            SuaveTransactionResponse::ConfidentialCall(response) => Some(response),
+        }
+    }
+
+    pub fn confidential_compute_result(&self) -> Option<&Bytes> {
+        self.as_confidential_call().map(|response| &response.confidential_compute_result)
+    }
+
+    pub fn request_record(&self) -> Option<&ConfidentialComputeRecord> {
+        self.as_confidential_call().map(|response| &response.request_record)
+    }
+
+}
+
+impl TryFrom<Transaction> for SuaveTransactionResponse {
+    type Error = SuaveTypesError;
+
+    fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+        let is_suave_tx = tx.transaction_type == Some(SuaveTransaction::TYPE)
+            || tx.other.contains_key("requestRecord");
+        if is_suave_tx {
+            Ok(SuaveTransactionResponse::ConfidentialCall(tx.try_into()?))
+        } else {
+            Ok(SuaveTransactionResponse::Ethereum(tx))
+        }
+    }
+}
+
+impl From<ConfidentialCallResponse> for SuaveTransactionResponse {
+    fn from(response: ConfidentialCallResponse) -> Self {
+        SuaveTransactionResponse::ConfidentialCall(response)
+    }
+}
+
+impl Serialize for SuaveTransactionResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SuaveTransactionResponse::Ethereum(tx) => tx.serialize(serializer),
+            SuaveTransactionResponse::ConfidentialCall(response) => response.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SuaveTransactionResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tx = SuaveRpcTransaction::deserialize(deserializer)?;
+        if !tx.is_suave_tx() {
+            return Ok(SuaveTransactionResponse::Ethereum(tx.into_transaction()));
+        }
+        let response = ConfidentialCallResponse::try_from(tx).map_err(serde::de::Error::custom)?;
+        Ok(SuaveTransactionResponse::ConfidentialCall(response))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+    use crate::test_utils::RESPONSE;
+
+    const TRANSFER: &str = r#"{"blockHash":"0x4c1f5e0b4d0a4f3cb3fbd0a0dc3b4fb4fd2c0f1a1de5d2ba6b1c2e1f1e0d9c8b","blockNumber":"0x1a2b","chainId":"0x1008c45","from":"0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a","gas":"0x5208","gasPrice":"0x8c9aca00","hash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","input":"0x","nonce":"0x44","r":"0x88ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0","s":"0x45e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a","to":"0xc803334c79650708daf3a3462ac4b48296b1352a","transactionIndex":"0x0","type":"0x0","v":"0x2011eb","value":"0xde0b6b3a7640000"}"#;
+
+    #[test]
+    fn test_parse_confidential_call() {
+        let response: SuaveTransactionResponse = serde_json::from_str(RESPONSE).unwrap();
+
+        assert!(response.is_confidential_call());
+        assert_eq!(response.tx_type(), Some(SuaveTxType::SuaveTransaction));
+        assert_eq!(response.hash(), B256::from_str("0x82f636c7bd91f9895f896b044e33528a2d116c65eea4c8e18c30c4577ae20ce2").unwrap());
+        assert_eq!(response.confidential_compute_result(), Some(&Bytes::from_str("0x0000000000000000000000000000000000000000000000000000000001ccb310").unwrap()));
+        assert_eq!(response.request_record().and_then(|record| record.nonce), Some(0x45));
+        assert!(response.as_ethereum().is_none());
+    }
+
+    #[test]
+    fn test_parse_ethereum_tx() {
+        let response: SuaveTransactionResponse = serde_json::from_str(TRANSFER).unwrap();
+
+        assert!(!response.is_confidential_call());
+        assert_eq!(response.tx_type(), Some(SuaveTxType::Legacy));
+        assert_eq!(response.from(), Address::from_str("0x19e7e376e7c213b7e7e7e46cc70a5dd086daff2a").unwrap());
+        assert_eq!(response.confidential_compute_result(), None);
+        assert_eq!(response.as_ethereum().map(|tx| tx.nonce), Some(0x44));
+    }
+
+    #[test]
+    fn test_parse_mixed_block_txs() {
+        let txs: Vec<SuaveTransactionResponse> = serde_json::from_str(&format!("[{TRANSFER},{RESPONSE}]")).unwrap();
+        assert_eq!(txs.iter().filter(|tx| tx.is_confidential_call()).count(), 1);
+
+        let reserialized = serde_json::to_string(&txs).unwrap();
+        let reparsed: Vec<SuaveTransactionResponse> = serde_json::from_str(&reserialized).unwrap();
+        assert_eq!(reparsed, txs);
+    }
+
+    #[test]
+    fn test_parse_matches_transaction_conversion() {
+        for json in [RESPONSE, TRANSFER] {
+            let parsed: SuaveTransactionResponse = serde_json::from_str(json).unwrap();
+            let tx: Transaction = serde_json::from_str(json).unwrap();
+            assert_eq!(parsed, SuaveTransactionResponse::try_from(tx).unwrap());
+        }
+
+        let response: ConfidentialCallResponse = serde_json::from_str(RESPONSE).unwrap();
+        assert!(response.transaction.other.is_empty());
+        assert_eq!(response.request_record.from, Some(response.transaction.from));
+    }
+
+    #[test]
+    fn test_parse_malformed_suave_tx() {
+        let malformed = TRANSFER.replace(r#""type":"0x0""#, r#""type":"0x50""#);
+        let err = serde_json::from_str::<SuaveTransactionResponse>(&malformed).unwrap_err();
+        assert!(err.to_string().contains("missing confidentialComputeResult"));
+    }
+
+}
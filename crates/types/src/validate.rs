@@ -0,0 +1,232 @@
+use core::fmt;
+use alloc::{string::String, vec::Vec};
+#[cfg(all(feature = "rlp", feature = "k256"))]
+use alloc::string::ToString;
+use alloy_primitives::{Address, ChainId, B256};
+use super::crecord::EMPTY_BYTES_HASH;
+use super::crequest::ConfidentialComputeRequest;
+use super::spec::SuaveSpec;
+
+
+/// Largest transaction geth's pool accepts, which bounds `input` and the confidential inputs together.
+pub const MAX_INPUT_SIZE: usize = 4 * 32 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Valid, but most likely not what the sender meant.
+    Warning,
+    /// The request can't be signed, or would be rejected.
+    Error,
+}
+
+/// Problem found by [`ConfidentialComputeRequest::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestIssue {
+    MissingField(&'static str),
+    /// The record doesn't commit to the confidential inputs of the request.
+    ConfidentialInputsHashMismatch { expected: B256, found: B256 },
+    /// `to` is the zero address, which is also what a contract creation maps to.
+    ZeroTarget,
+    ZeroGasPrice,
+    /// The chain has no preset, so the record is encoded with [`SuaveSpec::LATEST`].
+    UnknownChainId(ChainId),
+    /// `is_eip712` is set on a network without EIP-712 records.
+    Eip712NotSupported(ChainId),
+    UnrecoverableSignature(String),
+    /// The signature recovers to another address than `from`.
+    SignerMismatch { from: Address, recovered: Address },
+    InputTooLarge { len: usize, max: usize },
+}
+
+impl RequestIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            RequestIssue::ZeroTarget | RequestIssue::ZeroGasPrice | RequestIssue::UnknownChainId(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for RequestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestIssue::MissingField(field) => write!(f, "missing {field} field"),
+            RequestIssue::ConfidentialInputsHashMismatch { expected, found } => write!(
+                f,
+                "confidential inputs hash mismatch: expected {expected}, found {found}"
+            ),
+            RequestIssue::ZeroTarget => write!(f, "target is the zero address"),
+            RequestIssue::ZeroGasPrice => write!(f, "gas price is zero"),
+            RequestIssue::UnknownChainId(chain_id) => write!(f, "chain id {chain_id} has no preset, latest rules apply"),
+            RequestIssue::Eip712NotSupported(chain_id) => write!(f, "chain id {chain_id} has no EIP-712 records"),
+            RequestIssue::UnrecoverableSignature(reason) => write!(f, "unrecoverable signature: {reason}"),
+            RequestIssue::SignerMismatch { from, recovered } => write!(
+                f,
+                "signature recovers to {recovered}, not to sender {from}"
+            ),
+            RequestIssue::InputTooLarge { len, max } => write!(f, "input of {len} bytes exceeds {max} bytes"),
+        }
+    }
+}
+
+/// Every issue found by [`ConfidentialComputeRequest::validate`], in check order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestDiagnostics {
+    pub issues: Vec<RequestIssue>,
+}
+
+impl RequestDiagnostics {
+    /// No errors, though there may be warnings.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &RequestIssue> {
+        self.issues.iter().filter(|issue| issue.severity() == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &RequestIssue> {
+        self.issues.iter().filter(|issue| issue.severity() == Severity::Warning)
+    }
+}
+
+impl ConfidentialComputeRequest {
+
+    /// Checks the request for consistency before it is signed or sent.
+    /// The signature, if set, is only checked for recovery with the `rlp` and `k256` features.
+    pub fn validate(&self) -> RequestDiagnostics {
+        let record = &self.confidential_compute_record;
+        let mut issues = Vec::new();
+
+        let required = [
+            ("nonce", record.nonce.is_none()),
+            ("gas", record.gas.is_none()),
+            ("gas_price", record.gas_price.is_none()),
+            ("kettle_address", record.kettle_address.is_none()),
+            ("chain_id", record.chain_id.is_none()),
+        ];
+        issues.extend(required.into_iter().filter(|(_, missing)| *missing).map(|(field, _)| RequestIssue::MissingField(field)));
+
+        let found = self.confidential_inputs.hash();
+        let expected = record.confidential_inputs_hash.unwrap_or(EMPTY_BYTES_HASH);
+        if expected != found {
+            issues.push(RequestIssue::ConfidentialInputsHashMismatch { expected, found });
+        }
+        if record.to == Address::ZERO {
+            issues.push(RequestIssue::ZeroTarget);
+        }
+        if record.gas_price == Some(0) {
+            issues.push(RequestIssue::ZeroGasPrice);
+        }
+        if let Some(chain_id) = record.chain_id {
+            if ![SuaveSpec::RIGIL_CHAIN_ID, SuaveSpec::TOLIMAN_CHAIN_ID].contains(&chain_id) {
+                issues.push(RequestIssue::UnknownChainId(chain_id));
+            }
+            if record.is_eip712 && !SuaveSpec::from_chain_id(chain_id).eip712_records {
+                issues.push(RequestIssue::Eip712NotSupported(chain_id));
+            }
+        }
+        // Recovery can only fail for a reason of its own once every hashed field is set and valid.
+        #[cfg(all(feature = "rlp", feature = "k256"))]
+        if record.signature.is_some() && !issues.iter().any(|issue| {
+            matches!(issue, RequestIssue::MissingField(_) | RequestIssue::Eip712NotSupported(_))
+        }) {
+            match record.recover_signer() {
+                Ok(recovered) => match record.from {
+                    Some(from) if from != recovered => issues.push(RequestIssue::SignerMismatch { from, recovered }),
+                    _ => {}
+                },
+                Err(err) => issues.push(RequestIssue::UnrecoverableSignature(err.to_string())),
+            }
+        }
+        let len = record.input.len() + self.confidential_inputs.len();
+        if len > MAX_INPUT_SIZE {
+            issues.push(RequestIssue::InputTooLarge { len, max: MAX_INPUT_SIZE });
+        }
+
+        RequestDiagnostics { issues }
+    }
+
+}
+
+
+#[cfg(all(test, feature = "rpc"))]
+mod tests {
+    use super::*;
+    use crate::ConfidentialBytes;
+    use crate::test_utils::{devnet_record, response, sender};
+
+    fn devnet_request() -> ConfidentialComputeRequest {
+        ConfidentialComputeRequest { confidential_compute_record: devnet_record(), confidential_inputs: ConfidentialBytes::new() }
+    }
+
+    #[test]
+    fn test_validate_signed_request() {
+        let mut ccr = devnet_request();
+        let expected = ccr.confidential_compute_record.confidential_inputs_hash.unwrap();
+        let diagnostics = ccr.validate();
+        assert_eq!(diagnostics.issues, vec![
+            RequestIssue::ConfidentialInputsHashMismatch { expected, found: EMPTY_BYTES_HASH },
+            RequestIssue::UnknownChainId(0x067932),
+        ]);
+        assert!(!diagnostics.is_valid());
+
+        // Without a hash the record commits to empty inputs.
+        ccr.confidential_compute_record.confidential_inputs_hash = None;
+        ccr.confidential_compute_record.signature = None;
+        ccr.confidential_compute_record.chain_id = Some(SuaveSpec::TOLIMAN_CHAIN_ID);
+        assert_eq!(ccr.validate(), RequestDiagnostics::default());
+    }
+
+    #[test]
+    fn test_validate_reports_every_issue() {
+        let mut ccr = ConfidentialComputeRequest::default();
+        ccr.confidential_compute_record.gas_price = Some(0);
+        ccr.confidential_compute_record.input = vec![0; MAX_INPUT_SIZE].into();
+        ccr.confidential_inputs = ConfidentialBytes::from(&[1]);
+        let diagnostics = ccr.validate();
+        assert_eq!(diagnostics.issues, vec![
+            RequestIssue::MissingField("nonce"),
+            RequestIssue::MissingField("gas"),
+            RequestIssue::MissingField("kettle_address"),
+            RequestIssue::MissingField("chain_id"),
+            RequestIssue::ConfidentialInputsHashMismatch { expected: EMPTY_BYTES_HASH, found: alloy_primitives::keccak256([1]) },
+            RequestIssue::ZeroTarget,
+            RequestIssue::ZeroGasPrice,
+            RequestIssue::InputTooLarge { len: MAX_INPUT_SIZE + 1, max: MAX_INPUT_SIZE },
+        ]);
+        assert_eq!(diagnostics.warnings().count(), 2);
+        assert_eq!(diagnostics.errors().count(), 6);
+        assert_eq!(diagnostics.issues[4].severity(), Severity::Error);
+    }
+
+    #[test]
+    fn test_validate_warnings_only() {
+        let mut ccr = ConfidentialComputeRequest::new(devnet_request().confidential_compute_record, None);
+        ccr.confidential_compute_record.signature = None;
+        ccr.confidential_compute_record.to = Address::ZERO;
+        let diagnostics = ccr.validate();
+        assert_eq!(diagnostics.issues, vec![RequestIssue::ZeroTarget, RequestIssue::UnknownChainId(0x067932)]);
+        assert!(diagnostics.is_valid());
+        assert_eq!(diagnostics.issues[0].to_string(), "target is the zero address");
+    }
+
+    #[test]
+    fn test_validate_signature() {
+        // The Rigil request as the kettle returned it, with its sender.
+        let mut ccr = ConfidentialComputeRequest::new(response().request_record, None);
+        ccr.confidential_compute_record.from = Some(sender());
+        assert_eq!(ccr.validate(), RequestDiagnostics::default());
+
+        ccr.confidential_compute_record.from = Some(Address::repeat_byte(0x11));
+        assert_eq!(ccr.validate().issues, vec![RequestIssue::SignerMismatch { from: Address::repeat_byte(0x11), recovered: sender() }]);
+
+        // Rigil records can't be signed as EIP-712 typed data.
+        ccr.confidential_compute_record.from = None;
+        ccr.confidential_compute_record.is_eip712 = true;
+        let issues = ccr.validate().issues;
+        assert_eq!(issues, vec![RequestIssue::Eip712NotSupported(SuaveSpec::RIGIL_CHAIN_ID)]);
+        assert_eq!(issues[0].severity(), Severity::Error);
+    }
+
+}
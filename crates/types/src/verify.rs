@@ -0,0 +1,260 @@
+use std::fmt;
+use alloy_primitives::{Address, ChainId};
+use super::crequest::ConfidentialComputeRequest;
+use super::cresponse::ConfidentialCallResponse;
+use super::suavetx::SuaveTransaction;
+use super::error::Result;
+
+
+/// Outcome of recovering a signer and comparing it with the expected address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerCheck {
+    /// The signature recovers to the expected address.
+    Valid(Address),
+    /// The signature recovers to a different address, or there is nothing to compare with.
+    Mismatch { expected: Option<Address>, recovered: Address },
+    /// The signature is missing or could not be recovered.
+    Unrecoverable(String),
+}
+
+impl SignerCheck {
+    fn new(expected: Option<Address>, recovered: Result<Address>) -> Self {
+        match recovered {
+            Ok(recovered) if Some(recovered) == expected => SignerCheck::Valid(recovered),
+            Ok(recovered) => SignerCheck::Mismatch { expected, recovered },
+            Err(err) => SignerCheck::Unrecoverable(err.to_string()),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, SignerCheck::Valid(_))
+    }
+}
+
+/// Outcome of comparing the chain id of the kettle transaction with the one of the request record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainIdCheck {
+    Match(ChainId),
+    Mismatch { transaction: Option<ChainId>, record: Option<ChainId> },
+}
+
+impl ChainIdCheck {
+    fn new(transaction: Option<ChainId>, record: Option<ChainId>) -> Self {
+        match (transaction, record) {
+            (Some(transaction), Some(record)) if transaction == record => ChainIdCheck::Match(transaction),
+            _ => ChainIdCheck::Mismatch { transaction, record },
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ChainIdCheck::Match(_))
+    }
+}
+
+/// Verdict of [`ConfidentialCallResponse::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseVerdict {
+    /// Kettle signature on the 0x50 transaction against `request_record.kettle_address`.
+    pub kettle: SignerCheck,
+    /// Sender signature on the request record against the expected sender.
+    pub sender: SignerCheck,
+    pub chain_id: ChainIdCheck,
+}
+
+impl ResponseVerdict {
+    pub fn is_valid(&self) -> bool {
+        self.kettle.is_valid() && self.sender.is_valid() && self.chain_id.is_valid()
+    }
+}
+
+/// Request field that differs between the sent request and the record returned by the kettle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestField {
+    Nonce,
+    To,
+    Value,
+    Input,
+    Gas,
+    GasPrice,
+    KettleAddress,
+    ConfidentialInputsHash,
+}
+
+impl fmt::Display for RequestField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestField::Nonce => write!(f, "nonce"),
+            RequestField::To => write!(f, "to"),
+            RequestField::Value => write!(f, "value"),
+            RequestField::Input => write!(f, "input"),
+            RequestField::Gas => write!(f, "gas"),
+            RequestField::GasPrice => write!(f, "gas_price"),
+            RequestField::KettleAddress => write!(f, "kettle_address"),
+            RequestField::ConfidentialInputsHash => write!(f, "confidential_inputs_hash"),
+        }
+    }
+}
+
+impl ConfidentialCallResponse {
+
+    /// Checks that the response was signed by the kettle the record addresses,
+    /// that the record was signed by `expected_sender` and that the chain ids agree.
+    pub fn verify(&self, expected_sender: Address) -> ResponseVerdict {
+        let kettle_address = self.request_record.kettle_address;
+        let kettle = SignerCheck::new(
+            kettle_address,
+            SuaveTransaction::try_from(self).and_then(|suave_tx| suave_tx.recover_signer()),
+        );
+        let sender = SignerCheck::new(
+            Some(expected_sender),
+            self.request_record.recover_signer(),
+        );
+        let chain_id = ChainIdCheck::new(
+            self.transaction.chain_id,
+            self.request_record.chain_id,
+        );
+
+        ResponseVerdict { kettle, sender, chain_id }
+    }
+
+    /// Checks that the returned record is the one of `request`, with the confidential
+    /// inputs hash compared against the keccak of the inputs that were sent.
+    /// Returns the fields that diverge.
+    pub fn verify_request(&self, request: &ConfidentialComputeRequest) -> Result<(), Vec<RequestField>> {
+        let sent = &request.confidential_compute_record;
+        let received = &self.request_record;
+        let cinputs_hash = request.confidential_inputs.hash();
+
+        let mut mismatches = Vec::new();
+        if received.nonce != sent.nonce {
+            mismatches.push(RequestField::Nonce);
+        }
+        if received.to != sent.to {
+            mismatches.push(RequestField::To);
+        }
+        if received.value != sent.value {
+            mismatches.push(RequestField::Value);
+        }
+        if received.input != sent.input {
+            mismatches.push(RequestField::Input);
+        }
+        if received.gas != sent.gas {
+            mismatches.push(RequestField::Gas);
+        }
+        if received.gas_price != sent.gas_price {
+            mismatches.push(RequestField::GasPrice);
+        }
+        if received.kettle_address != sent.kettle_address {
+            mismatches.push(RequestField::KettleAddress);
+        }
+        if received.confidential_inputs_hash != Some(cinputs_hash) {
+            mismatches.push(RequestField::ConfidentialInputsHash);
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use alloy::primitives::{Bytes, U256};
+    use super::*;
+    use super::super::ConfidentialComputeRecord;
+    use crate::test_utils::{response, rigil_kettle, sender};
+
+    fn sent_request() -> ConfidentialComputeRequest {
+        let cc_record = ConfidentialComputeRecord {
+            nonce: Some(0x45),
+            to: Address::from_str("0xc803334c79650708daf3a3462ac4b48296b1352a").unwrap(),
+            gas: Some(0xf4240),
+            gas_price: Some(0x8c9aca00),
+            input: Bytes::from_str("0x50723553000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000074554485553445400").unwrap(),
+            kettle_address: Some(rigil_kettle()),
+            chain_id: Some(0x1008c45),
+            ..ConfidentialComputeRecord::default()
+        };
+        ConfidentialComputeRequest::new(cc_record, None)
+    }
+
+    #[test]
+    fn test_verify_request() {
+        assert_eq!(response().verify_request(&sent_request()), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_request_mismatches() {
+        let mut request = sent_request();
+        request.confidential_compute_record.nonce = Some(0x46);
+        request.confidential_compute_record.gas_price = None;
+        request.confidential_compute_record.value = U256::from(1);
+        request.confidential_compute_record.kettle_address = Some(Address::ZERO);
+        request.confidential_inputs = b"swapped".into();
+
+        let mismatches = response().verify_request(&request).unwrap_err();
+        assert_eq!(mismatches, vec![
+            RequestField::Nonce,
+            RequestField::Value,
+            RequestField::GasPrice,
+            RequestField::KettleAddress,
+            RequestField::ConfidentialInputsHash,
+        ]);
+    }
+
+    #[test]
+    fn test_verify_valid_response() {
+        let verdict = response().verify(sender());
+
+        assert_eq!(verdict.kettle, SignerCheck::Valid(rigil_kettle()));
+        assert_eq!(verdict.sender, SignerCheck::Valid(sender()));
+        assert_eq!(verdict.chain_id, ChainIdCheck::Match(0x1008c45));
+        assert!(verdict.is_valid());
+    }
+
+    #[test]
+    fn test_verify_wrong_sender() {
+        let verdict = response().verify(Address::ZERO);
+
+        assert_eq!(verdict.sender, SignerCheck::Mismatch { expected: Some(Address::ZERO), recovered: sender() });
+        assert!(verdict.kettle.is_valid());
+        assert!(!verdict.is_valid());
+    }
+
+    #[test]
+    fn test_verify_tampered_result() {
+        let mut response = response();
+        response.confidential_compute_result = Bytes::from_static(&[1]);
+        let verdict = response.verify(sender());
+
+        assert!(matches!(verdict.kettle, SignerCheck::Mismatch { expected: Some(k), .. } if k == rigil_kettle()));
+        assert!(verdict.sender.is_valid());
+        assert!(!verdict.is_valid());
+    }
+
+    #[test]
+    fn test_verify_missing_kettle_signature() {
+        let mut response = response();
+        response.transaction.signature = None;
+        let verdict = response.verify(sender());
+
+        assert_eq!(verdict.kettle, SignerCheck::Unrecoverable(String::from("missing signature field")));
+        assert!(!verdict.is_valid());
+    }
+
+    #[test]
+    fn test_verify_chain_id_mismatch() {
+        let mut response = response();
+        response.transaction.chain_id = Some(1);
+        let verdict = response.verify(sender());
+
+        assert_eq!(verdict.chain_id, ChainIdCheck::Mismatch { transaction: Some(1), record: Some(0x1008c45) });
+        assert!(!verdict.is_valid());
+    }
+
+}
@@ -1,5 +1,5 @@
 use std::str::FromStr;
-use eyre::Result;
+use eyre::{Result, OptionExt};
 use alloy::{
     providers::{Provider, ProviderBuilder},
     primitives::{Address, Bytes}, 
@@ -41,8 +41,11 @@ async fn main() -> Result<()> {
 
     // Send tx
     let pending_tx = call_builder.send().await?;
-    let confidential_request_res = provider.get_transaction_by_hash(*pending_tx.tx_hash()).await?;
-    println!("{:#?}", confidential_request_res);
+    let tx_response = provider.get_transaction_by_hash(*pending_tx.tx_hash()).await?;
+    let cc_response = tx_response.into_confidential_call()
+        .ok_or_eyre("response is not a confidential call")?;
+    let price = cc_response.decode_result::<BinanceOracle::queryLatestPriceCall>()?.price;
+    println!("Price: {:?}", price.wrapping_to::<u128>());
 
     Ok(())
 }
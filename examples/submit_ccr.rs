@@ -1,14 +1,20 @@
 use std::str::FromStr;
 use eyre::{Result, OptionExt};
 use alloy::{
-    primitives::{Address, Bytes, B256, U256}, 
+    primitives::{Address, Bytes}, 
     providers::{Provider, ProviderBuilder}, 
     signers::wallet::LocalWallet,
     network::TransactionBuilder, 
+    sol,
 };
 use suave_alloy::prelude::*;
 
 
+sol! {
+    function queryLatestPrice(string ticker) returns (uint256 price);
+}
+
+
 #[tokio::main]
 
 async fn main() -> Result<()> {
@@ -35,15 +41,14 @@ async fn main() -> Result<()> {
         .with_confidential_inputs(cinputs); // No need to specify it if no confidential input
     
     // Send CCR
-    let result = provider.send_transaction(ccr).await?;
-    let tx_hash = B256::from_slice(&result.tx_hash().to_vec());
+    let result = provider.send_transaction(ccr.into()).await?;
+    let tx_hash = *result.tx_hash();
 
     // Obtain CCR Response with record and compute-result
     let tx_response = provider.get_transaction_by_hash(tx_hash).await?;
-    println!("{tx_response:#?}");
-
-    let price = U256::try_from_be_slice(&tx_response.confidential_compute_result.to_vec())
-        .ok_or_eyre("conf result is not U256")?;
+    let cc_response = tx_response.into_confidential_call()
+        .ok_or_eyre("response is not a confidential call")?;
+    let price = cc_response.decode_result::<queryLatestPriceCall>()?.price;
     println!("Price: {:?}", price.wrapping_to::<u128>());
 
     Ok(())